@@ -1,6 +1,8 @@
-use std::path::PathBuf;
+use std::{path::PathBuf, time::Duration};
 
-use clap::{Args, Parser, Subcommand};
+use clap::{Args, FromArgMatches, Parser, Subcommand, ValueEnum};
+
+use crate::validator::DefaultValidatorFlags;
 
 pub fn parse_args() -> KittyArgs {
     KittyArgs::parse()
@@ -20,10 +22,14 @@ pub struct KittyArgs {
 
 #[derive(Subcommand, Debug)]
 pub enum KittySubcommand {
+    Clean(CleanArgs),
     Config(ConfigArgs),
     Debug(DebugArgs),
+    FetchSubmission(FetchSubmissionArgs),
     Get(GetArgs),
     Open(OpenArgs),
+    Show(ShowArgs),
+    Submissions(SubmissionsArgs),
     Submit(SubmitArgs),
     Test(TestArgs),
 
@@ -34,6 +40,12 @@ pub enum KittySubcommand {
     /// shown in the output of this command.
     Langs,
 
+    /// List the templates in kitty's config directory
+    ///
+    /// File templates are shown with the language they are for, and the
+    /// template that 'kitty get' uses by default for each language is marked.
+    Templates,
+
     /// Updates kitty to the latest version
     ///
     /// The currently installed binary will be replaced with the one at
@@ -76,19 +88,36 @@ pub enum ConfigSubcommand {
 }
 
 /// Fetches a problem from Kattis by creating a solution folder of the same name
-/// and downloading the official test cases. If you have defined a template, it
-/// will be copied into your solution folder.
+/// and downloading the official test cases and the problem statement. Details
+/// such as the problem's name and limits are saved in a problem.yml file. If you
+/// have defined a template, it will be copied into your solution folder.
 ///
 /// You can create your own templates for your preferred programming languages.
 /// In kitty's config directory, create a 'templates' subfolder, and inside that,
 /// create a file such as template.java in which you define your Java template.
+/// You can keep several templates per language, such as fastio.cpp and
+/// plain.cpp, and choose between them with --template.
 #[derive(Args, Debug)]
 pub struct GetArgs {
     /// The ID of the problem to fetch from Kattis.
     ///
     /// You can find the id in the URL of the problem page on kattis:
     /// open.kattis.com/problems/<PROBLEM ID>
-    pub problem_id: String,
+    #[arg(required_unless_present = "contest")]
+    pub problem_id: Option<String>,
+
+    /// Fetch all problems in a Kattis contest instead of a single problem.
+    ///
+    /// Give either the URL of the contest, such as
+    /// https://open.kattis.com/contests/<CONTEST ID>, or just its ID. A
+    /// solution folder is created for each problem in the current folder.
+    #[arg(short, long, value_name = "CONTEST", conflicts_with = "problem_id")]
+    pub contest: Option<String>,
+
+    /// Name the solution folders of a contest's problems after their letters
+    /// (A, B, C, ...) instead of their IDs.
+    #[arg(long, default_value_t = false)]
+    pub letters: bool,
 
     /// If present, remove the host name from the problem ID in templates.
     ///
@@ -104,6 +133,19 @@ pub struct GetArgs {
     /// js for JavaScript, etc.).
     #[arg(short, long)]
     pub lang: Option<String>,
+
+    /// Name of the template to copy into the solution folder.
+    ///
+    /// Templates live in the 'templates' subfolder of kitty's config directory.
+    /// A template is either a file such as fastio.cpp, which is used as the
+    /// solution file, or a folder whose contents are all copied. If a file
+    /// template exists for several languages, use --lang to pick one. Run
+    /// 'kitty templates' to see the templates you have.
+    ///
+    /// Defaults to the language's 'default_template' in your config file, or
+    /// 'template' if it has none.
+    #[arg(short, long, value_name = "NAME")]
+    pub template: Option<String>,
 }
 
 /// Runs a solution through the test cases
@@ -146,10 +188,54 @@ pub struct TestArgs {
     #[arg(long, default_value_t = false)]
     pub fetch: bool,
 
-    /// Display how long each test case takes to execute.
+    /// Display how long each test case takes to execute and, where it can be
+    /// measured, how much memory it uses at most.
     #[arg(short, long, default_value_t = false)]
     pub time: bool,
 
+    /// Kills the solution if a test case runs for longer than this many
+    /// seconds, reporting it as a time limit exceeded.
+    ///
+    /// Defaults to the problem's CPU time limit on Kattis as saved in the
+    /// problem.yml file by 'kitty get'. The limit is scaled by the
+    /// 'time_limit_multiplier' of the solution's language in your config file,
+    /// if it has one.
+    #[arg(long, value_name = "SECONDS", value_parser = parse_seconds)]
+    pub time_limit: Option<Duration>,
+
+    /// Reports a memory limit exceeded if the solution's peak memory usage
+    /// exceeds this many megabytes.
+    ///
+    /// Defaults to the problem's memory limit on Kattis as saved in the
+    /// problem.yml file by 'kitty get'.
+    ///
+    /// On Linux, the solution's address space is additionally capped to stop
+    /// runaway solutions. Be aware that some runtimes, such as the JVM, reserve
+    /// a lot of memory up front and may fail to start with a low limit.
+    #[arg(long, value_name = "MB", value_parser = parse_megabytes)]
+    pub memory_limit: Option<u64>,
+
+    /// The number of test cases to run in parallel.
+    ///
+    /// Results are still shown in the usual order. Since running tests in
+    /// parallel makes time measurements less reliable, tests are run one at a
+    /// time when --time is given unless --parallel-timing is also given.
+    #[arg(short, long, default_value_t = 1, value_parser = clap::value_parser!(u32).range(1..))]
+    pub jobs: u32,
+
+    /// Run tests in parallel even when --time is given.
+    #[arg(long, default_value_t = false)]
+    pub parallel_timing: bool,
+
+    /// Compiles the solution even if it has not changed since it was last
+    /// compiled.
+    ///
+    /// kitty remembers the source code, compile command and compiler used for
+    /// the last compilation of each solution and skips compiling when none of
+    /// them have changed.
+    #[arg(long, default_value_t = false)]
+    pub rebuild: bool,
+
     /// Re-runs tests every time the source file changes.
     #[arg(short, long, default_value_t = false)]
     pub watch: bool,
@@ -171,9 +257,166 @@ pub struct TestArgs {
     #[arg(short = 'F', long)]
     pub filter: Option<String>,
 
+    /// Only run the tests in this group. Can be given multiple times.
+    ///
+    /// Test cases can be organised in subfolders of the test folder, such as
+    /// test/sample and test/secret, and the subfolder is the test's group.
+    /// Subgroups are included, so --group secret also runs the tests in
+    /// test/secret/group1.
+    ///
+    /// If the solution folder has no test folder but has a 'data' folder, as in
+    /// Kattis problem packages, the tests are read from there instead.
+    #[arg(short, long, value_name = "GROUP")]
+    pub group: Vec<String>,
+
+    /// Path to an output validator program to judge the solution's output
+    /// with instead of comparing it with the .ans file.
+    ///
+    /// By default, kitty uses the validator in the 'validator' subfolder of
+    /// your solution folder if it exists, or the one given in the 'validator'
+    /// field of a problem.yml file in the solution folder.
+    ///
+    /// Validators follow the same conventions as on Kattis: they are run as
+    /// 'validator input_file answer_file feedback_dir' with the solution's
+    /// output piped into stdin, and they must exit with code 42 to accept the
+    /// output and 43 to reject it. Messages written to judgemessage.txt or
+    /// teammessage.txt in the feedback folder are shown for wrong answers.
+    #[arg(long, value_name = "PATH")]
+    pub validator: Option<PathBuf>,
+
+    /// Path to an interactor program for interactive problems.
+    ///
+    /// By default, kitty uses the interactor in the 'interactor' subfolder of
+    /// your solution folder if it exists, or the one given in the 'interactor'
+    /// field of a problem.yml file in the solution folder.
+    ///
+    /// The interactor is run as 'interactor input_file answer_file
+    /// feedback_dir' alongside the solution. Everything the interactor writes
+    /// to stdout is sent to the solution's stdin and vice versa. Like output
+    /// validators, it must exit with code 42 to accept the solution and 43 to
+    /// reject it. The transcript of the interaction is shown for wrong answers.
+    #[arg(long, value_name = "PATH")]
+    pub interactor: Option<PathBuf>,
+
+    /// Accepts floating-point numbers in the output if their absolute or
+    /// relative error is at most this value.
+    ///
+    /// This is the same as passing 'float_tolerance <EPSILON>' as a validator
+    /// flag.
+    #[arg(long, value_name = "EPSILON", value_parser = parse_float_tolerance)]
+    pub float_tolerance: Option<f64>,
+
+    /// Flags for comparing the output with the expected answer, written like
+    /// the flags of Kattis' default output validator.
+    ///
+    /// The available flags are 'case_sensitive', 'space_change_sensitive',
+    /// 'float_absolute_tolerance <EPSILON>', 'float_relative_tolerance
+    /// <EPSILON>' and 'float_tolerance <EPSILON>'. By default, comparisons are
+    /// case insensitive and any amount of whitespace between tokens is
+    /// accepted.
+    ///
+    /// Flags can also be given for a single problem by writing them in the
+    /// 'validator_flags' field of a problem.yml file in the solution folder.
+    /// For example:
+    ///
+    /// validator_flags: float_tolerance 1e-6
+    #[arg(long, value_name = "FLAGS", allow_hyphen_values = true)]
+    pub validator_flags: Option<String>,
+
     /// Shows the solution's stderr output in case of a wrong answer.
     #[arg(short = 'e', long, default_value_t = false)]
     stderr: bool,
+
+    /// How to report the test results.
+    ///
+    /// 'json' prints a single JSON object with a record for each test case and
+    /// a summary once all tests have run. 'jsonl' prints each record on its
+    /// own line as soon as the test case finishes, followed by the summary.
+    /// Records include the test name, verdict, running time in seconds and,
+    /// for failed tests, excerpts of the expected answer, the actual output
+    /// and stderr. Long excerpts end with '... (truncated)'.
+    #[arg(long, value_enum, default_value_t = OutputFormat::Human)]
+    pub format: OutputFormat,
+
+    /// Saves the output of the solution next to the test files, such that it
+    /// can be opened in an external diff tool.
+    ///
+    /// For a test case 'test/<name>.in', stdout is saved to 'test/<name>.out'
+    /// and stderr to 'test/<name>.err'. For interactive problems, the
+    /// transcript is also saved to 'test/<name>.transcript'. By default, only
+    /// the output of failed tests is saved, and previously saved output of
+    /// tests that now pass is removed.
+    #[arg(
+        long,
+        value_enum,
+        value_name = "WHICH",
+        num_args = 0..=1,
+        default_missing_value = "failed"
+    )]
+    pub save_output: Option<SaveOutput>,
+
+    /// Also writes the test results to this file as JUnit XML.
+    ///
+    /// The test cases are reported as a test suite named after the problem,
+    /// so testing several problems with a separate file for each works with CI
+    /// systems that accept multiple JUnit files.
+    #[arg(long, value_name = "FILE")]
+    pub junit: Option<PathBuf>,
+
+    /// Also writes the test results to this file using the Test Anything
+    /// Protocol (TAP).
+    #[arg(long, value_name = "FILE")]
+    pub tap: Option<PathBuf>,
+}
+
+impl Default for TestArgs {
+    /// The options that are used when running 'kitty test' without arguments.
+    fn default() -> Self {
+        let command = Self::augment_args(clap::Command::new("test"));
+        let matches = command.get_matches_from(["test"]);
+
+        Self::from_arg_matches(&matches).expect("the default test options should be valid")
+    }
+}
+
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SaveOutput {
+    /// Save the output of failed tests
+    Failed,
+    /// Save the output of all tests
+    All,
+}
+
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum OutputFormat {
+    /// Human-readable output
+    Human,
+    /// A single JSON document
+    Json,
+    /// One JSON object per line (JSON Lines)
+    Jsonl,
+}
+
+/// Removes compiled programs and other build files from solution folders
+///
+/// The files that are removed are based on the languages in your kitty.yml.
+/// For compiled languages, the executable is removed by default, and more
+/// files can be listed in a language's artifacts setting. Source code, test
+/// files and the problem.md, problem.yml and problem.yaml files are never
+/// removed, and folders are only removed from inside a language's build folder.
+#[derive(Args, Debug)]
+pub struct CleanArgs {
+    /// The path to the solution folder to clean.
+    #[arg(default_value = ".")]
+    pub path: PathBuf,
+
+    /// Also clean all solution folders inside the given folder.
+    #[arg(short, long, default_value_t = false)]
+    pub recursive: bool,
+
+    /// List the files that would be removed without removing them.
+    #[arg(long, default_value_t = false)]
+    pub dry_run: bool,
 }
 
 /// Opens a problem in the browser
@@ -184,13 +427,83 @@ pub struct OpenArgs {
     pub problem_id: Option<String>,
 }
 
+/// Shows the problem statement in the terminal
+///
+/// When you fetch a problem with 'kitty get', its statement is saved as
+/// problem.md in the solution folder. Math is written as TeX between dollar
+/// signs.
+#[derive(Args, Debug)]
+pub struct ShowArgs {
+    /// The path to the solution folder of the problem to show.
+    #[arg(default_value = ".")]
+    pub path: PathBuf,
+
+    /// Download the problem statement from Kattis again, replacing the saved
+    /// one. The problem ID is read from the 'id' field of the problem.yml
+    /// file, such as for contest problems saved in folders named by letter, or
+    /// otherwise taken from the name of the solution folder.
+    #[arg(long, default_value_t = false)]
+    pub fetch: bool,
+}
+
+/// Lists your submissions on Kattis
+///
+/// Your most recent submissions are shown first along with their verdicts and
+/// CPU times. Use the options to narrow the list down to a problem or verdict
+/// and to page through older submissions.
+#[derive(Args, Debug)]
+pub struct SubmissionsArgs {
+    /// Only show submissions to the problem with this ID.
+    pub problem_id: Option<String>,
+
+    /// Only show submissions whose verdict contains this text, such as
+    /// 'accepted' or 'wrong answer'. Case is ignored.
+    #[arg(long)]
+    pub verdict: Option<String>,
+
+    /// The number of submissions to show.
+    #[arg(short = 'n', long, default_value_t = 20, value_parser = clap::value_parser!(u32).range(1..))]
+    pub limit: u32,
+
+    /// The page of submissions to show, starting from 1.
+    #[arg(short, long, default_value_t = 1, value_parser = clap::value_parser!(u32).range(1..))]
+    pub page: u32,
+}
+
+/// Downloads the source code of one of your submissions
+///
+/// The files are saved in the problem's solution folder. kitty looks for it in
+/// the current folder and creates a folder named after the problem if there is
+/// none.
+#[derive(Args, Debug)]
+pub struct FetchSubmissionArgs {
+    /// The ID of the submission, as shown by 'kitty submissions'.
+    #[arg(required_unless_present = "latest_accepted")]
+    pub submission_id: Option<String>,
+
+    /// Download your latest accepted submission to the problem with this ID
+    /// instead.
+    #[arg(long, value_name = "PROBLEM ID", conflicts_with = "submission_id")]
+    pub latest_accepted: Option<String>,
+
+    /// The folder to save the files in instead of the problem's solution
+    /// folder.
+    #[arg(short, long)]
+    pub path: Option<PathBuf>,
+
+    /// Overwrite files that already exist.
+    #[arg(long, default_value_t = false)]
+    pub force: bool,
+}
+
 /// Submits a solution to Kattis
 #[derive(Args, Debug)]
 pub struct SubmitArgs {
     /// The path to the solution folder you want to submit.
     ///
-    /// The name of the folder is used as the problem ID when submitting to
-    /// Kattis.
+    /// The problem ID used when submitting to Kattis is read from the 'id'
+    /// field of the problem.yml file in the folder, or otherwise taken from
+    /// the name of the folder.
     #[arg(default_value = ".")]
     pub path: PathBuf,
 
@@ -219,6 +532,26 @@ pub struct SubmitArgs {
     /// Open the submission on Kattis in your browser.
     #[arg(short, long, default_value_t = false)]
     pub open: bool,
+
+    /// Run the solution through the test cases before submitting it, and only
+    /// submit it if all of them pass.
+    ///
+    /// Set 'require_tests' under 'submit' in your config file to always do
+    /// this.
+    #[arg(short, long, default_value_t = false)]
+    pub test: bool,
+
+    /// Submit the solution even if it fails the test cases.
+    #[arg(long, default_value_t = false)]
+    pub force: bool,
+
+    /// The class containing the program's main method, for languages such as
+    /// Java, Kotlin and Scala.
+    ///
+    /// By default, kitty finds it in the source code, or uses the
+    /// 'main_class' of the language in your config file if it has one.
+    #[arg(long, value_name = "CLASS")]
+    pub main_class: Option<String>,
 }
 
 /// Instead of using the static test files in the test folder, use custom
@@ -276,6 +609,26 @@ pub struct DebugInputArgs {
     /// The number of test cases to generate and execute.
     #[arg(short, long, default_value = "100")]
     pub num_tests: usize,
+
+    /// Kills the solution if a test case runs for longer than this many
+    /// seconds.
+    ///
+    /// See the test command for more.
+    #[arg(long, value_name = "SECONDS", value_parser = parse_seconds)]
+    pub time_limit: Option<Duration>,
+
+    /// Reports a memory limit exceeded if the solution's peak memory usage
+    /// exceeds this many megabytes.
+    ///
+    /// See the test command for more.
+    #[arg(long, value_name = "MB", value_parser = parse_megabytes)]
+    pub memory_limit: Option<u64>,
+
+    /// Path to an interactor program for interactive problems.
+    ///
+    /// See the test command for more.
+    #[arg(long, value_name = "PATH")]
+    pub interactor: Option<PathBuf>,
 }
 
 /// For cases where you can write a solution that is correct but too slow for
@@ -300,6 +653,30 @@ pub struct DebugAnswerArgs {
     pub answer_validator_path: Option<PathBuf>,
 }
 
+fn parse_seconds(s: &str) -> Result<Duration, String> {
+    let secs = s
+        .parse::<f64>()
+        .map_err(|_| format!("'{s}' is not a number"))?;
+
+    if secs <= 0.0 {
+        return Err(format!("'{s}' is not a positive number of seconds"));
+    }
+
+    Duration::try_from_secs_f64(secs).map_err(|_| format!("'{s}' is not a valid number of seconds"))
+}
+
+fn parse_float_tolerance(s: &str) -> Result<f64, String> {
+    DefaultValidatorFlags::parse_tolerance(s)
+        .ok_or_else(|| format!("'{s}' is not a finite, non-negative number"))
+}
+
+fn parse_megabytes(s: &str) -> Result<u64, String> {
+    match s.parse::<u64>() {
+        Ok(0) | Err(_) => Err(format!("'{s}' is not a positive number of megabytes")),
+        Ok(megabytes) => Ok(megabytes),
+    }
+}
+
 impl KittyArgs {
     pub fn should_show_wrong_answer_stderr(&self) -> bool {
         match &self.subcommand {
@@ -0,0 +1,241 @@
+use std::{
+    collections::BTreeSet,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use colored::Colorize;
+use eyre::Context;
+
+use crate::{
+    cli::CleanArgs,
+    compile_cache,
+    problem::{PROBLEM_FILE_NAME, PROBLEM_PACKAGE_FILE_NAME},
+    solution::{find_test_dir, get_all_files_with_known_extension},
+    statement::STATEMENT_FILE_NAME,
+    utils::get_full_path,
+    App,
+};
+
+pub async fn clean(app: &App, args: &CleanArgs) -> crate::Result<()> {
+    eyre::ensure!(
+        args.path.is_dir(),
+        "The path does not point to a folder: '{}'",
+        args.path.display()
+    );
+
+    let folders = if args.recursive {
+        find_folders(&args.path)?
+    } else {
+        vec![args.path.clone()]
+    };
+
+    let mut num_removed = 0;
+    let mut num_cleaned_folders = 0;
+
+    for folder in folders {
+        let num_removed_in_folder = clean_folder(app, &folder, args.dry_run)
+            .wrap_err_with(|| format!("Failed to clean '{}'", folder.display()))?;
+
+        if num_removed_in_folder > 0 {
+            num_removed += num_removed_in_folder;
+            num_cleaned_folders += 1;
+        }
+    }
+
+    if num_removed == 0 {
+        println!("No build files found");
+    } else if args.dry_run {
+        println!(
+            "\n{num_removed} build file(s) in {num_cleaned_folders} solution folder(s) would be removed"
+        );
+    } else {
+        println!(
+            "\n{num_removed} build file(s) removed from {num_cleaned_folders} solution folder(s)"
+        );
+    }
+
+    Ok(())
+}
+
+/// Removes the build files of all source files in the folder. Returns the
+/// number of removed files.
+fn clean_folder(app: &App, folder: &Path, dry_run: bool) -> crate::Result<usize> {
+    let full_folder = get_full_path(folder)?;
+    let source_files = get_all_files_with_known_extension(app, folder)?
+        .into_iter()
+        .map(get_full_path)
+        .collect::<crate::Result<BTreeSet<_>>>()?;
+
+    let mut artifacts = BTreeSet::new();
+    let mut build_dirs = BTreeSet::new();
+
+    for source_file in &source_files {
+        let lang = app.config.try_lang_from_file(source_file)?;
+
+        for pattern in lang.get_artifacts(source_file)? {
+            artifacts.extend(expand_wildcards(&pattern)?);
+        }
+
+        build_dirs.extend(lang.build_dir(source_file)?);
+    }
+
+    let test_dir = find_test_dir(&full_folder);
+    let protected_files = [
+        STATEMENT_FILE_NAME,
+        PROBLEM_FILE_NAME,
+        PROBLEM_PACKAGE_FILE_NAME,
+    ]
+    .map(|file_name| full_folder.join(file_name));
+
+    // Build folders that are, or contain, the solution folder do not count, as
+    // folders inside them are not only build files.
+    let build_dirs_in_solution = build_dirs
+        .iter()
+        .filter(|build_dir| !full_folder.starts_with(build_dir))
+        .collect::<Vec<_>>();
+
+    // Patterns must never match the source code, the tests, the problem's
+    // description or anything outside the solution and build folders. Folders
+    // are only removed from build folders, since they are removed with all of
+    // their contents.
+    artifacts.retain(|path| {
+        let is_in_allowed_folder = path.starts_with(&full_folder)
+            || build_dirs
+                .iter()
+                .any(|build_dir| path.starts_with(build_dir));
+
+        let is_protected = *path == full_folder
+            || path.starts_with(&test_dir)
+            || protected_files.contains(path)
+            || source_files.contains(path);
+
+        let is_removable_folder = build_dirs_in_solution
+            .iter()
+            .any(|build_dir| path.starts_with(build_dir) && path != *build_dir);
+
+        is_in_allowed_folder && !is_protected && (!path.is_dir() || is_removable_folder)
+    });
+
+    for path in &artifacts {
+        let shown_path = folder.join(path.strip_prefix(&full_folder).unwrap_or(path));
+
+        if dry_run {
+            println!("{} {}", "Would remove".bright_cyan(), shown_path.display());
+            continue;
+        }
+
+        let removed = if path.is_dir() {
+            fs::remove_dir_all(path)
+        } else {
+            fs::remove_file(path)
+        };
+
+        removed.wrap_err_with(|| format!("Failed to remove '{}'", shown_path.display()))?;
+        println!("{} {}", "Removed".bright_green(), shown_path.display());
+    }
+
+    if !dry_run && !artifacts.is_empty() {
+        for source_file in &source_files {
+            compile_cache::forget(app, source_file)?;
+        }
+
+        for build_dir in &build_dirs {
+            remove_empty_folders(build_dir, &full_folder);
+        }
+    }
+
+    Ok(artifacts.len())
+}
+
+/// Finds the folder and all folders inside it, skipping hidden folders.
+fn find_folders(root: &Path) -> crate::Result<Vec<PathBuf>> {
+    let mut folders = vec![root.to_path_buf()];
+    let mut i = 0;
+
+    while let Some(folder) = folders.get(i) {
+        let entries = fs::read_dir(folder)
+            .wrap_err_with(|| format!("Failed to read folder '{}'", folder.display()))?;
+
+        let mut subfolders = entries
+            .filter_map(Result::ok)
+            .filter(|entry| entry.file_type().is_ok_and(|t| t.is_dir()))
+            .filter(|entry| !entry.file_name().to_string_lossy().starts_with('.'))
+            .map(|entry| entry.path())
+            .collect::<Vec<_>>();
+
+        subfolders.sort();
+        folders.extend(subfolders);
+        i += 1;
+    }
+
+    Ok(folders)
+}
+
+/// Finds the existing paths matching a path whose last component may contain
+/// `*` and `?` wildcards.
+fn expand_wildcards(pattern: &Path) -> crate::Result<Vec<PathBuf>> {
+    let name = pattern.file_name().unwrap_or_default().to_string_lossy();
+
+    if !name.contains(['*', '?']) {
+        return Ok(pattern
+            .exists()
+            .then(|| pattern.to_path_buf())
+            .into_iter()
+            .collect());
+    }
+
+    let Some(dir) = pattern.parent().filter(|dir| dir.is_dir()) else {
+        return Ok(Vec::new());
+    };
+
+    let entries =
+        fs::read_dir(dir).wrap_err_with(|| format!("Failed to read folder '{}'", dir.display()))?;
+
+    let matches = entries
+        .filter_map(Result::ok)
+        .filter(|entry| matches_wildcard(&name, &entry.file_name().to_string_lossy()))
+        .map(|entry| entry.path())
+        .collect();
+
+    Ok(matches)
+}
+
+fn matches_wildcard(pattern: &str, name: &str) -> bool {
+    let pattern = pattern.chars().collect::<Vec<_>>();
+    let name = name.chars().collect::<Vec<_>>();
+
+    // matches[j] is whether the pattern seen so far matches the first j
+    // characters of the name.
+    let mut matches = vec![false; name.len() + 1];
+    matches[0] = true;
+
+    for p in pattern {
+        let previous = matches.clone();
+        matches[0] = p == '*' && previous[0];
+
+        for j in 1..=name.len() {
+            matches[j] = match p {
+                '*' => previous[j] || matches[j - 1],
+                '?' => previous[j - 1],
+                c => previous[j - 1] && c == name[j - 1],
+            };
+        }
+    }
+
+    matches[name.len()]
+}
+
+/// Removes the folder and its parents up to the solution folder if they are
+/// empty.
+fn remove_empty_folders(folder: &Path, solution_folder: &Path) {
+    let empty_folders = folder
+        .ancestors()
+        .take_while(|dir| dir.starts_with(solution_folder) && *dir != solution_folder);
+
+    for dir in empty_folders {
+        if fs::remove_dir(dir).is_err() {
+            break;
+        }
+    }
+}
@@ -12,11 +12,13 @@ use eyre::{bail, eyre, Context};
 
 use crate::{
     cli::{DebugAnswerArgs, DebugArgs, DebugInputArgs, DebugSubcommand},
-    commands::test::{FAILURE, SUCCESS},
     config::language::{ExecuteProgramCommands, Language},
+    problem::ProblemMetadata,
+    reporter::{FAILURE, SUCCESS},
     solution::{get_all_files_with_known_extension, Solution, SolutionOptions},
-    test_io::{run_compile_cmd, run_test, run_with_input, TestCaseError, TestCaseIO},
+    test_io::{run_compile_cmd, run_test, run_with_input, TestCaseError, TestCaseIO, TestSetup},
     utils::{resolve_and_get_file_name, RunningAverager, TimedPrinter},
+    validator::{resolve_interactor, OutputValidator},
     App,
 };
 
@@ -47,6 +49,30 @@ pub async fn debug(app: &App, args: &DebugArgs) -> crate::Result<()> {
 
     let generators = GeneratorTestCase::new(app, &solution, args);
 
+    // Without answers to compare with, the output is not checked, so a custom
+    // validator is not run.
+    let validator = if generators.should_check_answer() {
+        OutputValidator::for_solution_dir(app, &solution.dir, Default::default())?
+    } else {
+        OutputValidator::Default(Default::default())
+    };
+    let interactor = resolve_interactor(app, &solution.dir, input_args.interactor.as_deref())?;
+
+    // The limits from Kattis are used unless others are given, like when testing.
+    let metadata = ProblemMetadata::load(&solution.dir)?;
+    let limits = solution.resource_limits(
+        input_args.time_limit.or(metadata.time_limit),
+        input_args.memory_limit.or(metadata.memory_limit),
+    );
+
+    let run_cmd = solution.lang.get_run_cmd(&solution.file)?;
+    let setup = TestSetup {
+        run_cmd: &run_cmd,
+        limits,
+        validator: &validator,
+        interactor: interactor.as_ref(),
+    };
+
     let num_tests = input_args.num_tests;
 
     let mut printer = TimedPrinter::new(Duration::from_millis(30));
@@ -58,7 +84,7 @@ pub async fn debug(app: &App, args: &DebugArgs) -> crate::Result<()> {
             n == num_tests,
         )?;
 
-        let result = test_solution_with_generators(app, &solution, &generators);
+        let result = test_solution_with_generators(app, &generators, &setup);
 
         if result.is_err() {
             println!("{FAILURE}\n");
@@ -74,7 +100,7 @@ pub async fn debug(app: &App, args: &DebugArgs) -> crate::Result<()> {
         if let Err(failure) = outcome {
             println!("{FAILURE}\n");
 
-            failure.test_case_error.print(app);
+            failure.test_case_error.print(app)?;
 
             println!("{}:", "Input".bright_red());
             println!("{}\n", failure.test_case_error.input().trim_end());
@@ -108,16 +134,19 @@ pub async fn debug(app: &App, args: &DebugArgs) -> crate::Result<()> {
 
 fn test_solution_with_generators(
     app: &App,
-    solution: &Solution,
     test_case: &GeneratorTestCase<'_>,
+    setup: &TestSetup,
 ) -> crate::Result<Result<GeneratorSuccess, GeneratorError>> {
     let start_time = Instant::now();
-    let test_result = run_test(app, &solution.lang.get_run_cmd(&solution.file)?, test_case)?;
+    let test_result = run_test(app, setup, test_case)?;
     let execution_time = start_time.elapsed();
 
     if let Err(test_case_error) = test_result {
         let should_return_error = match test_case_error {
-            TestCaseError::RuntimeError { .. } => true,
+            TestCaseError::RuntimeError { .. }
+            | TestCaseError::TimeLimitExceeded { .. }
+            | TestCaseError::MemoryLimitExceeded { .. }
+            | TestCaseError::WrongInteraction { .. } => true,
             TestCaseError::WrongAnswer { .. } if test_case.should_check_answer() => true,
             _ => false,
         };
@@ -381,6 +410,9 @@ fn write_failure_to_files(solution: &Solution, failure: &GeneratorError) -> crat
     let kind = match failure.test_case_error {
         TestCaseError::WrongAnswer { .. } => "wrong-answer",
         TestCaseError::RuntimeError { .. } => "runtime-error",
+        TestCaseError::TimeLimitExceeded { .. } => "time-limit-exceeded",
+        TestCaseError::MemoryLimitExceeded { .. } => "memory-limit-exceeded",
+        TestCaseError::WrongInteraction { .. } => "wrong-interaction",
     };
 
     let file_basename = format!("{timestamp}-{kind}");
@@ -415,13 +447,22 @@ fn write_failure_to_files(solution: &Solution, failure: &GeneratorError) -> crat
 
             println!("\nTo use these as part of normal `kitty test` runs, move the .in/.ans files to the test folder of your solution.")
         }
-        TestCaseError::RuntimeError { stdout, stderr, .. } => {
+        TestCaseError::RuntimeError { stdout, stderr, .. }
+        | TestCaseError::TimeLimitExceeded { stdout, stderr, .. }
+        | TestCaseError::MemoryLimitExceeded { stdout, stderr, .. } => {
             write_file(
                 "your solution's output",
                 &format!("{file_basename}.output"),
                 &[stdout.clone(), stderr.clone()].join("\n"),
             )?;
         }
+        TestCaseError::WrongInteraction { transcript, .. } => {
+            write_file(
+                "transcript",
+                &format!("{file_basename}.transcript"),
+                transcript,
+            )?;
+        }
     }
 
     println!(
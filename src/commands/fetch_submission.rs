@@ -0,0 +1,122 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use colored::Colorize;
+use eyre::{bail, Context};
+
+use crate::{
+    cli::FetchSubmissionArgs,
+    problem::{problem_id_from_folder, problem_id_is_legal},
+    submission::{fetch_submission_source, find_submissions},
+    App,
+};
+
+pub async fn fetch_submission(app: &App, args: &FetchSubmissionArgs) -> crate::Result<()> {
+    app.client.login(app).await?;
+
+    let submission_id = match (&args.submission_id, &args.latest_accepted) {
+        (Some(submission_id), _) => submission_id.clone(),
+        (None, Some(problem_id)) => find_latest_accepted(app, problem_id).await?,
+        (None, None) => bail!("You must give either a submission ID or --latest-accepted"),
+    };
+
+    eyre::ensure!(
+        !submission_id.is_empty() && submission_id.chars().all(|c| c.is_ascii_digit()),
+        "Submission ID '{submission_id}' is not valid"
+    );
+
+    let source = fetch_submission_source(app, &submission_id).await?;
+    let problem_id = source
+        .problem_id
+        .as_deref()
+        .or(args.latest_accepted.as_deref());
+
+    let solution_dir = match (&args.path, problem_id) {
+        (Some(path), _) => path.clone(),
+        (None, Some(problem_id)) => find_solution_dir(problem_id)?,
+        (None, None) => bail!(
+            "Could not find the problem of submission {submission_id}. Use --path to choose where to save it."
+        ),
+    };
+
+    let existing_files = source
+        .files
+        .iter()
+        .map(|file| solution_dir.join(&file.name))
+        .filter(|path| path.exists())
+        .map(|path| path.display().to_string())
+        .collect::<Vec<_>>();
+
+    eyre::ensure!(
+        args.force || existing_files.is_empty(),
+        "{} already exists. Use --force to overwrite it.",
+        existing_files.join(", ")
+    );
+
+    fs::create_dir_all(&solution_dir)
+        .wrap_err_with(|| format!("Failed to create {}", solution_dir.display()))?;
+
+    for file in &source.files {
+        let path = solution_dir.join(&file.name);
+
+        fs::write(&path, &file.contents)
+            .wrap_err_with(|| format!("Failed to write {}", path.display()))?;
+
+        println!("{} {}", "Saved".bright_green(), path.display());
+    }
+
+    Ok(())
+}
+
+async fn find_latest_accepted(app: &App, problem_id: &str) -> crate::Result<String> {
+    eyre::ensure!(
+        problem_id_is_legal(problem_id),
+        "Problem ID '{problem_id}' is not valid"
+    );
+
+    let submissions = find_submissions(app, 1, |submission| {
+        submission.is_for_problem(problem_id) && submission.is_accepted()
+    })
+    .await?;
+
+    submissions
+        .into_iter()
+        .next()
+        .map(|submission| submission.id)
+        .ok_or_else(|| {
+            eyre::eyre!("Could not find an accepted submission to '{problem_id}' among your recent submissions")
+        })
+}
+
+/// Finds the solution folder of the problem in the current folder. This is
+/// either the current folder itself, a folder named after the problem, or a
+/// folder whose problem file has the problem's ID, such as the folders of a
+/// contest's problems named after their letters. If there is none, a folder
+/// named after the problem is used.
+fn find_solution_dir(problem_id: &str) -> crate::Result<PathBuf> {
+    let is_solution_dir = |dir: &Path| problem_id_from_folder(dir).is_ok_and(|id| id == problem_id);
+
+    let cwd = Path::new(".");
+
+    if is_solution_dir(cwd) {
+        return Ok(cwd.to_path_buf());
+    }
+
+    let named_dir = cwd.join(problem_id);
+
+    if named_dir.is_dir() {
+        return Ok(named_dir);
+    }
+
+    let entries = fs::read_dir(cwd).wrap_err("Failed to read the current folder")?;
+
+    let solution_dir = entries
+        .filter_map(Result::ok)
+        .map(|entry| entry.path())
+        .filter(|path| path.is_dir())
+        .find(|path| is_solution_dir(path));
+
+    Ok(solution_dir.unwrap_or(named_dir))
+}
@@ -6,65 +6,235 @@ use std::{
 
 use colored::Colorize;
 use eyre::{bail, Context};
-use reqwest::StatusCode;
+use reqwest::{Client, StatusCode};
 use zip::ZipArchive;
 
 use crate::{
     cli::GetArgs,
     config::language::Language,
-    problem::{make_problem_sample_tests_zip_url, make_problem_url, problem_id_is_legal},
+    contest::{fetch_contest_problems, Contest, ContestProblem},
+    problem::{
+        fetch_problem_page_at, make_problem_sample_tests_zip_url, make_problem_url,
+        make_problem_url_on_host, make_sample_tests_zip_url, problem_id_is_legal, save_problem_id,
+        ProblemMetadata,
+    },
     solution::get_test_dir,
+    statement::{statement_to_markdown, STATEMENT_FILE_NAME},
+    template::{self, RenderedTemplate, TemplateVariables},
     App,
 };
 
 pub async fn get(app: &App, args: &GetArgs) -> crate::Result<()> {
-    if !problem_id_is_legal(&args.problem_id) {
-        bail!("The given problem ID is invalid. It must only contain alphanumeric characters and periods.");
+    match (&args.contest, &args.problem_id) {
+        (Some(contest), _) => get_contest(app, args, contest).await,
+        (None, Some(problem_id)) => get_problem(app, args, problem_id).await,
+        (None, None) => bail!("You must give either a problem ID or a contest"),
     }
+}
 
-    if !problem_exists(app, &args.problem_id).await? {
-        bail!("Problem '{}' does not exist", args.problem_id)
+async fn get_problem(app: &App, args: &GetArgs, problem_id: &str) -> crate::Result<()> {
+    eyre::ensure!(
+        !args.letters,
+        "The --letters flag can only be used with --contest"
+    );
+
+    if !problem_id_is_legal(problem_id) {
+        bail!("The given problem ID is invalid. It must only contain alphanumeric characters and periods.");
     }
 
-    let solution_dir = create_solution_dir(&args.problem_id)?;
+    let problem_url = make_problem_url(app, problem_id)?;
+    let download = download_problem(
+        app.client.client.clone(),
+        problem_id.to_string(),
+        problem_url,
+    );
 
-    fetch_tests(app, &solution_dir, &args.problem_id)
-        .await
-        .wrap_err("Failed to fetch test cases")?;
+    let Some(problem) = download.await? else {
+        bail!("Problem '{problem_id}' does not exist")
+    };
 
-    populate_template(app, args, &solution_dir)?;
+    let solution_dir = create_solution_dir(problem_id)?;
+
+    create_solution(app, args, &problem, &solution_dir)?;
 
     println!(
-        "{} solution folder for {}",
+        "{} solution folder for {problem_id}",
         "Created".bright_green(),
-        args.problem_id
     );
 
     Ok(())
 }
 
-async fn problem_exists(app: &App, problem_id: &str) -> crate::Result<bool> {
-    let url = make_problem_url(app, problem_id)?;
-    let response = app
-        .client
-        .get(&url)
-        .send()
+async fn get_contest(app: &App, args: &GetArgs, contest: &str) -> crate::Result<()> {
+    let contest = Contest::parse(app, contest)?;
+
+    app.client.login(app).await?;
+
+    let problems = fetch_contest_problems(app, &contest).await?;
+
+    println!(
+        "Fetching {} problems from contest {}",
+        problems.len(),
+        contest.id
+    );
+
+    // All problems are downloaded at once, but the solution folders are
+    // created one at a time in the contest's order.
+    let downloads = problems
+        .iter()
+        .map(|problem| {
+            let problem_url = make_problem_url_on_host(&contest.host_url, &problem.id);
+            tokio::spawn(download_problem(
+                app.client.client.clone(),
+                problem.id.clone(),
+                problem_url,
+            ))
+        })
+        .collect::<Vec<_>>();
+
+    let mut num_failed = 0;
+
+    for (problem, download) in problems.iter().zip(downloads) {
+        let folder_name = if args.letters {
+            &problem.letter
+        } else {
+            &problem.id
+        };
+
+        let result = match download.await {
+            Ok(download) => create_contest_solution(app, args, problem, folder_name, download),
+            Err(e) => Err(e).wrap_err("Failed to download the problem"),
+        };
+
+        match result {
+            Ok(()) => println!(
+                "{} solution folder {folder_name} for {} ({})",
+                "Created".bright_green(),
+                problem.id,
+                problem.letter
+            ),
+            Err(e) => {
+                num_failed += 1;
+                eprintln!(
+                    "{}: Failed to fetch {} ({}): {e}",
+                    "Error".bright_red(),
+                    problem.id,
+                    problem.letter
+                );
+            }
+        }
+    }
+
+    eyre::ensure!(
+        num_failed == 0,
+        "Failed to fetch {num_failed} of the {} problems",
+        problems.len()
+    );
+
+    Ok(())
+}
+
+fn create_contest_solution(
+    app: &App,
+    args: &GetArgs,
+    problem: &ContestProblem,
+    folder_name: &str,
+    download: crate::Result<Option<DownloadedProblem>>,
+) -> crate::Result<()> {
+    let downloaded =
+        download?.ok_or_else(|| eyre::eyre!("Problem '{}' does not exist", problem.id))?;
+
+    let solution_dir = create_solution_dir(folder_name)?;
+
+    // Other commands find the problem ID in the problem file when the folder
+    // is not named after the problem.
+    if folder_name != problem.id {
+        save_problem_id(&solution_dir, &problem.id).wrap_err("Failed to save the problem ID")?;
+    }
+
+    create_solution(app, args, &downloaded, &solution_dir)
+}
+
+/// A problem's page and sample tests as downloaded from Kattis.
+struct DownloadedProblem {
+    id: String,
+    page: String,
+    samples: Option<Vec<u8>>,
+}
+
+/// Downloads the page and sample tests of a problem. Returns `None` if the
+/// problem does not exist.
+async fn download_problem(
+    client: Client,
+    problem_id: String,
+    problem_url: String,
+) -> crate::Result<Option<DownloadedProblem>> {
+    let Some(page) = fetch_problem_page_at(&client, &problem_url).await? else {
+        return Ok(None);
+    };
+
+    let samples = download_samples(&client, &make_sample_tests_zip_url(&problem_url))
         .await
-        .wrap_err("Failed to send request to Kattis")?;
+        .wrap_err("Failed to fetch test cases")?;
 
-    match response.status() {
-        status if status.is_success() => Ok(true),
-        StatusCode::NOT_FOUND => Ok(false),
-        _ => bail!(
-            "Failed to get problem from Kattis (http status code: {})",
-            response.status()
-        ),
+    Ok(Some(DownloadedProblem {
+        id: problem_id,
+        page,
+        samples,
+    }))
+}
+
+/// Fills a new solution folder with the tests, statement, details and
+/// template of the problem.
+fn create_solution(
+    app: &App,
+    args: &GetArgs,
+    problem: &DownloadedProblem,
+    solution_dir: &Path,
+) -> crate::Result<()> {
+    save_tests(solution_dir, problem.samples.as_deref()).wrap_err("Failed to fetch test cases")?;
+
+    save_statement(app, solution_dir, &problem.page)
+        .wrap_err("Failed to save the problem statement")?;
+
+    let metadata = ProblemMetadata::from_problem_page(&problem.page);
+
+    if !metadata.is_empty() {
+        metadata
+            .save(solution_dir)
+            .wrap_err("Failed to save the problem details")?;
     }
+
+    populate_template(app, args, &problem.id, solution_dir, &metadata)
+}
+
+/// Converts the statement on the problem page to Markdown and saves it in the
+/// solution folder. Pages without a statement are skipped with a warning.
+pub fn save_statement(
+    app: &App,
+    solution_dir: impl AsRef<Path>,
+    page_html: &str,
+) -> crate::Result<()> {
+    let host_name = &app.config.try_kattisrc()?.kattis.host_name;
+    let base_url = format!("https://{host_name}");
+
+    let Some(statement) = statement_to_markdown(page_html, &base_url) else {
+        eprintln!(
+            "{}: Could not find the problem statement on the problem page",
+            "Warning".bright_yellow()
+        );
+        return Ok(());
+    };
+
+    let statement_file = solution_dir.as_ref().join(STATEMENT_FILE_NAME);
+
+    fs::write(&statement_file, statement)
+        .wrap_err_with(|| format!("Failed to write {}", statement_file.display()))
 }
 
-fn create_solution_dir(problem_id: &str) -> crate::Result<PathBuf> {
+fn create_solution_dir(folder_name: &str) -> crate::Result<PathBuf> {
     let cwd = env::current_dir().wrap_err("Failed to get current working directory")?;
-    let solution_dir = cwd.join(problem_id);
+    let solution_dir = cwd.join(folder_name);
 
     let result = fs::create_dir(&solution_dir);
 
@@ -84,14 +254,17 @@ pub async fn fetch_tests(
     solution_dir: impl AsRef<Path>,
     problem_id: &str,
 ) -> crate::Result<()> {
-    let test_dir = get_test_dir(solution_dir);
+    let zip_url = make_problem_sample_tests_zip_url(app, problem_id)?;
+    let samples = download_samples(&app.client, &zip_url).await?;
 
-    fs::create_dir(&test_dir).wrap_err("Failed to create test files directory")?;
+    save_tests(solution_dir, samples.as_deref())
+}
 
-    let zip_url = make_problem_sample_tests_zip_url(app, problem_id)?;
-    let zip_response = app
-        .client
-        .get(&zip_url)
+/// Downloads the zip file with a problem's sample tests. Returns `None` if the
+/// problem has no sample tests.
+async fn download_samples(client: &Client, zip_url: &str) -> crate::Result<Option<Vec<u8>>> {
+    let zip_response = client
+        .get(zip_url)
         .send()
         .await
         .wrap_err("Failed to send request to Kattis")?;
@@ -99,15 +272,29 @@ pub async fn fetch_tests(
     let status = zip_response.status();
     if !status.is_success() {
         if status == StatusCode::NOT_FOUND {
-            return Ok(());
+            return Ok(None);
         }
 
         bail!("Failed to fetch tests from Kattis (http status code: {status})",);
     }
 
     let response_bytes = zip_response.bytes().await?;
+
+    Ok(Some(response_bytes.to_vec()))
+}
+
+/// Creates the test folder and extracts the sample tests into it.
+fn save_tests(solution_dir: impl AsRef<Path>, samples_zip: Option<&[u8]>) -> crate::Result<()> {
+    let test_dir = get_test_dir(solution_dir);
+
+    fs::create_dir(&test_dir).wrap_err("Failed to create test files directory")?;
+
+    let Some(samples_zip) = samples_zip else {
+        return Ok(());
+    };
+
     let mut tmpfile = tempfile::tempfile()?;
-    tmpfile.write_all(&response_bytes)?;
+    tmpfile.write_all(samples_zip)?;
 
     let mut zip = ZipArchive::new(tmpfile)?;
 
@@ -129,36 +316,105 @@ pub async fn fetch_tests(
 fn populate_template(
     app: &App,
     args: &GetArgs,
-    solution_dir: impl AsRef<Path>,
+    problem_id: &str,
+    solution_dir: &Path,
+    metadata: &ProblemMetadata,
 ) -> crate::Result<()> {
+    let file_name = if args.no_domain {
+        problem_id.split('.').next_back().unwrap()
+    } else {
+        problem_id
+    };
+    let variables = template_variables(app, problem_id, file_name, metadata);
+
+    if let Some(template_name) = &args.template {
+        let template_dir = app.config.templates_dir_path().join(template_name);
+
+        if template_dir.is_dir() {
+            return copy_template_dir(&template_dir, solution_dir, &variables)
+                .wrap_err("Failed to populate the solution folder with your template");
+        }
+    }
+
     let lang =
-        match &args.lang {
-            Some(lang) => Some(app.config.lang_from_file_ext(lang).ok_or_else(|| {
+        match (&args.lang, &args.template) {
+            (Some(lang), _) => Some(app.config.lang_from_file_ext(lang).ok_or_else(|| {
                 eyre::eyre!("Could not find a language to use for .{} files", lang)
             })?),
-            None => app.config.default_language(),
+            (None, Some(template_name)) => Some(find_template_lang(app, template_name)?),
+            (None, None) => app.config.default_language(),
         };
 
     if let Some(language) = lang {
-        copy_template_with_lang(app, args, solution_dir, language)
+        copy_template_with_lang(app, args, solution_dir, file_name, language, &variables)
             .wrap_err("Failed to populate the solution folder with your template")?;
     }
 
     Ok(())
 }
 
+/// Finds the language of a named file template. The default language is
+/// preferred if there are templates with the name for several languages.
+fn find_template_lang<'a>(app: &'a App, template_name: &str) -> crate::Result<&'a Language> {
+    let has_template =
+        |lang: &Language| app.config.template_file_path(template_name, lang).is_file();
+
+    if let Some(lang) = app.config.default_language().filter(|l| has_template(l)) {
+        return Ok(lang);
+    }
+
+    let langs = app
+        .config
+        .languages
+        .iter()
+        .filter(|lang| has_template(lang))
+        .collect::<Vec<_>>();
+
+    match langs[..] {
+        [lang] => Ok(lang),
+        [] => bail!(
+            "Could not find the template '{template_name}'. Templates are files such as {template_name}.cpp or folders in {}",
+            app.config.templates_dir_path().display()
+        ),
+        _ => bail!(
+            "The template '{template_name}' exists for several languages. Use --lang to choose one of {}",
+            langs
+                .iter()
+                .map(|lang| lang.file_ext())
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+    }
+}
+
 fn copy_template_with_lang(
     app: &App,
     args: &GetArgs,
-    solution_dir: impl AsRef<Path>,
+    solution_dir: &Path,
+    file_name: &str,
     lang: &Language,
+    variables: &TemplateVariables,
 ) -> crate::Result<()> {
-    let templates_dir = app.config.templates_dir_path();
-    let template_file = templates_dir
-        .join("template")
-        .with_extension(lang.file_ext());
+    let template_name = args
+        .template
+        .as_deref()
+        .unwrap_or_else(|| lang.default_template());
+    let template_file = app.config.template_file_path(template_name, lang);
 
     if !template_file.exists() {
+        // Languages can also use a folder template by default.
+        let template_dir = app.config.templates_dir_path().join(template_name);
+
+        if args.template.is_none() && template_dir.is_dir() {
+            return copy_template_dir(&template_dir, solution_dir, variables);
+        }
+
+        eyre::ensure!(
+            args.template.is_none(),
+            "Could not find the template '{template_name}' for {lang}. Expected to find it at {}",
+            template_file.display()
+        );
+
         println!(
             "{} does not exist. kitty will skip creating the solution file for you.",
             template_file.display()
@@ -166,23 +422,130 @@ fn copy_template_with_lang(
         return Ok(());
     }
 
-    let file_name = if args.no_domain {
-        args.problem_id.split('.').last().unwrap()
-    } else {
-        &args.problem_id
+    let solution_file = solution_dir.join(file_name).with_extension(lang.file_ext());
+
+    copy_template_file(&template_file, &solution_file, variables)
+}
+
+/// Copies a folder template into the solution folder. Variables are filled in
+/// in both the names and the contents of the files. Existing files are kept.
+fn copy_template_dir(
+    template_dir: &Path,
+    dest_dir: &Path,
+    variables: &TemplateVariables,
+) -> crate::Result<()> {
+    let entries = fs::read_dir(template_dir)
+        .wrap_err_with(|| format!("Failed to read {}", template_dir.display()))?;
+
+    for entry in entries {
+        let entry = entry.wrap_err_with(|| format!("Failed to read {}", template_dir.display()))?;
+        let template_path = entry.path();
+
+        let file_name = entry.file_name().to_string_lossy().into_owned();
+        let rendered_name = template::render(&file_name, variables);
+        warn_about_unknown_variables(&rendered_name, &template_path);
+
+        let dest_path = dest_dir.join(rendered_name.text);
+
+        if template_path.is_dir() {
+            fs::create_dir_all(&dest_path)
+                .wrap_err_with(|| format!("Failed to create {}", dest_path.display()))?;
+            copy_template_dir(&template_path, &dest_path, variables)?;
+        } else if dest_path.exists() {
+            eprintln!(
+                "{}: Skipping {} since it already exists",
+                "Warning".bright_yellow(),
+                dest_path.display()
+            );
+        } else {
+            copy_template_file(&template_path, &dest_path, variables)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Copies a template file with its variables filled in. Files that are not
+/// text, such as images, are copied as they are.
+fn copy_template_file(
+    template_file: &Path,
+    dest_file: &Path,
+    variables: &TemplateVariables,
+) -> crate::Result<()> {
+    let template = fs::read(template_file)
+        .wrap_err_with(|| eyre::eyre!("failed to read {}", template_file.display()))?;
+
+    let contents = match String::from_utf8(template) {
+        Ok(template) => {
+            let rendered = template::render(&template, variables);
+            warn_about_unknown_variables(&rendered, template_file);
+            rendered.text.into_bytes()
+        }
+        Err(e) => e.into_bytes(),
     };
 
-    let template = fs::read_to_string(&template_file)
+    fs::write(dest_file, contents)
+        .wrap_err_with(|| eyre::eyre!("failed to write {}", dest_file.display()))?;
+
+    // Keep scripts in templates executable.
+    let permissions = fs::metadata(template_file)
         .wrap_err_with(|| eyre::eyre!("failed to read {}", template_file.display()))?
-        .replace("$FILENAME", file_name);
+        .permissions();
+    fs::set_permissions(dest_file, permissions)
+        .wrap_err_with(|| eyre::eyre!("failed to set permissions of {}", dest_file.display()))
+}
 
-    let solution_file = solution_dir
-        .as_ref()
-        .join(file_name)
-        .with_extension(lang.file_ext());
+fn warn_about_unknown_variables(rendered: &RenderedTemplate, template_path: &Path) {
+    for name in &rendered.unknown_variables {
+        eprintln!(
+            "{}: Unknown variable ${name} in {}",
+            "Warning".bright_yellow(),
+            template_path.display()
+        );
+    }
+}
 
-    fs::write(&solution_file, template)
-        .wrap_err_with(|| eyre::eyre!("failed to write {}", solution_file.display()))?;
+fn template_variables(
+    app: &App,
+    problem_id: &str,
+    file_name: &str,
+    metadata: &ProblemMetadata,
+) -> TemplateVariables {
+    let kattisrc = app.config.try_kattisrc().ok();
+    let mut variables = TemplateVariables::default();
+
+    variables
+        .set("FILENAME", Some(file_name.to_string()))
+        .set("PROBLEM_ID", Some(problem_id.to_string()))
+        .set(
+            "PROBLEM_NAME",
+            Some(
+                metadata
+                    .name
+                    .clone()
+                    .unwrap_or_else(|| problem_id.to_string()),
+            ),
+        )
+        .set("PROBLEM_URL", make_problem_url(app, problem_id).ok())
+        .set(
+            "TIME_LIMIT",
+            metadata
+                .time_limit
+                .map(|limit| limit.as_secs_f64().to_string()),
+        )
+        .set(
+            "MEMORY_LIMIT",
+            metadata.memory_limit.map(|mb| mb.to_string()),
+        )
+        .set("DATE", Some(template::today()))
+        .set(
+            "AUTHOR",
+            kattisrc.map(|kattisrc| kattisrc.user.username.clone()),
+        )
+        .set(
+            "HOST",
+            kattisrc.map(|kattisrc| kattisrc.kattis.host_name.clone()),
+        );
 
-    Ok(())
+    variables
 }
@@ -1,17 +1,27 @@
+mod clean;
 mod config;
 mod debug;
+mod fetch_submission;
 mod get;
 mod langs;
 mod open;
+mod show;
+mod submissions;
 mod submit;
+mod templates;
 mod test;
 mod update;
 
+pub use clean::clean;
 pub use config::config;
 pub use debug::debug;
+pub use fetch_submission::fetch_submission;
 pub use get::get;
 pub use langs::langs;
 pub use open::open;
+pub use show::show;
+pub use submissions::submissions;
 pub use submit::submit;
+pub use templates::templates;
 pub use test::test;
 pub use update::update;
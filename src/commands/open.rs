@@ -5,8 +5,7 @@ use eyre::Context;
 
 use crate::{
     cli::OpenArgs,
-    problem::{make_problem_url, problem_id_is_legal},
-    utils::resolve_and_get_file_name,
+    problem::{make_problem_url, problem_id_from_folder, problem_id_is_legal},
     App,
 };
 
@@ -15,8 +14,8 @@ pub async fn open(app: &App, args: &OpenArgs) -> crate::Result<()> {
         Some(problem_id) => problem_id.clone(),
         None => env::current_dir()
             .wrap_err("Failed to get current directory")
-            .and_then(resolve_and_get_file_name)
-            .wrap_err("Failed to get name of current directory")?,
+            .and_then(problem_id_from_folder)
+            .wrap_err("Failed to get the problem ID of the current directory")?,
     };
 
     if !problem_id_is_legal(&problem_id) {
@@ -0,0 +1,49 @@
+use std::fs;
+
+use colored::Colorize;
+use eyre::Context;
+
+use crate::{
+    cli::ShowArgs,
+    commands::get::save_statement,
+    problem::{fetch_problem_page, problem_id_from_folder},
+    statement::{render_markdown, STATEMENT_FILE_NAME},
+    utils::get_full_path,
+    App,
+};
+
+pub async fn show(app: &App, args: &ShowArgs) -> crate::Result<()> {
+    let solution_dir = get_full_path(&args.path)?;
+
+    eyre::ensure!(
+        solution_dir.is_dir(),
+        "The path does not point to a folder: '{}'",
+        solution_dir.display().to_string().underline()
+    );
+
+    let statement_file = solution_dir.join(STATEMENT_FILE_NAME);
+
+    if args.fetch {
+        let problem_id = problem_id_from_folder(&solution_dir)
+            .wrap_err("Failed to extract problem ID from the solution folder")?;
+
+        let problem_page = fetch_problem_page(app, &problem_id)
+            .await?
+            .ok_or_else(|| eyre::eyre!("Problem '{problem_id}' does not exist"))?;
+
+        save_statement(app, &solution_dir, &problem_page)
+            .wrap_err("Failed to save the problem statement")?;
+    }
+
+    eyre::ensure!(
+        statement_file.is_file(),
+        "No problem statement found in the solution folder. Run `kitty show --fetch` to download it from Kattis."
+    );
+
+    let statement = fs::read_to_string(&statement_file)
+        .wrap_err_with(|| format!("Failed to read {}", statement_file.display()))?;
+
+    println!("{}", render_markdown(&statement));
+
+    Ok(())
+}
@@ -0,0 +1,121 @@
+use colored::Colorize;
+
+use crate::{
+    cli::SubmissionsArgs,
+    problem::problem_id_is_legal,
+    submission::{find_submissions, SubmissionSummary},
+    App,
+};
+
+pub async fn submissions(app: &App, args: &SubmissionsArgs) -> crate::Result<()> {
+    if let Some(problem_id) = &args.problem_id {
+        eyre::ensure!(
+            problem_id_is_legal(problem_id),
+            "Problem ID '{problem_id}' is not valid"
+        );
+    }
+
+    app.client.login(app).await?;
+
+    let limit = args.limit as usize;
+    let skip = (args.page as usize - 1) * limit;
+
+    // One more than needed is found to tell whether there is a next page.
+    let matching = find_submissions(app, skip + limit + 1, |submission| {
+        is_match(args, submission)
+    })
+    .await?;
+
+    let has_next_page = matching.len() > skip + limit;
+    let shown = matching
+        .into_iter()
+        .skip(skip)
+        .take(limit)
+        .collect::<Vec<_>>();
+
+    if shown.is_empty() {
+        println!("No submissions found");
+        return Ok(());
+    }
+
+    print_table(&shown);
+
+    if has_next_page {
+        println!("\nRun with --page {} to see more", args.page + 1);
+    }
+
+    Ok(())
+}
+
+fn is_match(args: &SubmissionsArgs, submission: &SubmissionSummary) -> bool {
+    let is_right_problem = args
+        .problem_id
+        .as_ref()
+        .map_or(true, |problem_id| submission.is_for_problem(problem_id));
+
+    let has_right_verdict = args.verdict.as_ref().map_or(true, |verdict| {
+        submission
+            .verdict
+            .to_lowercase()
+            .contains(&verdict.to_lowercase())
+    });
+
+    is_right_problem && has_right_verdict
+}
+
+fn print_table(submissions: &[SubmissionSummary]) {
+    let rows = submissions
+        .iter()
+        .map(|submission| {
+            [
+                submission.id.clone(),
+                submission
+                    .problem_id
+                    .clone()
+                    .unwrap_or_else(|| submission.problem_name.clone()),
+                submission.language.clone(),
+                submission.verdict.clone(),
+                submission
+                    .cpu_time
+                    .clone()
+                    .unwrap_or_else(|| "-".to_string()),
+                submission.submitted_at.clone(),
+            ]
+        })
+        .collect::<Vec<_>>();
+
+    let headers = ["ID", "Problem", "Language", "Verdict", "CPU", "Submitted"];
+    let mut widths = headers.map(str::len);
+
+    for row in &rows {
+        for (width, value) in widths.iter_mut().zip(row) {
+            *width = (*width).max(value.chars().count());
+        }
+    }
+
+    let header = headers
+        .iter()
+        .zip(widths)
+        .map(|(header, width)| format!("{header:width$}").bright_cyan().to_string())
+        .collect::<Vec<_>>();
+    println!("{}", header.join("  ").trim_end());
+
+    for (row, submission) in rows.iter().zip(submissions) {
+        let cells = row
+            .iter()
+            .zip(widths)
+            .enumerate()
+            .map(|(i, (value, width))| {
+                let cell = format!("{value:width$}");
+
+                match i {
+                    3 if submission.is_accepted() => cell.bright_green().to_string(),
+                    3 => cell.bright_red().to_string(),
+                    _ => cell,
+                }
+            })
+            .collect::<Vec<_>>();
+
+        println!("{}", cells.join("  ").trim_end());
+    }
+}
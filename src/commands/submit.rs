@@ -13,8 +13,12 @@ use serde::Deserialize;
 use tokio::time::sleep;
 
 use crate::{
-    cli::SubmitArgs,
+    cli::{SubmitArgs, TestArgs},
+    commands::test::run_tests,
+    main_class::{detect_main_class, needs_main_class},
+    problem::ProblemMetadata,
     solution::{Solution, SolutionOptions},
+    submission::make_submission_url,
     utils::{prompt_bool, resolve_and_get_file_name},
     App,
 };
@@ -37,17 +41,44 @@ pub async fn submit(app: &App, args: &SubmitArgs) -> crate::Result<()> {
 
     let file_name = resolve_and_get_file_name(&solution.file)?;
 
-    println!("{}:  {}", "Problem".bright_cyan(), &solution.id);
+    let metadata = ProblemMetadata::load(&solution.dir)?;
+
+    match &metadata.name {
+        Some(name) => println!("{}:  {name} ({})", "Problem".bright_cyan(), &solution.id),
+        None => println!("{}:  {}", "Problem".bright_cyan(), &solution.id),
+    }
+
     println!("{}: {}", "Language".bright_cyan(), &solution.lang);
     println!("{}:     {}", "File".bright_cyan(), &file_name);
 
+    let main_class = resolve_main_class(args, &solution)?;
+
+    // The main class is only worth showing if it is not simply the file name.
+    if Some(main_class.as_str()) != file_name.split('.').next() {
+        println!("{}: {main_class}", "Main class".bright_cyan());
+    }
+
+    if let Some(limits) = format_limits(&metadata) {
+        println!("{}:   {limits}", "Limits".bright_cyan());
+    }
+
+    if let Some(difficulty) = &metadata.difficulty {
+        println!("{}: {difficulty}", "Difficulty".bright_cyan());
+    }
+
+    solution.lang.ensure_known_by_kattis()?;
+
+    if args.test || app.config.submit.require_tests {
+        test_before_submitting(app, args, &solution)?;
+    }
+
     if !args.yes && !prompt_bool("Should this be submitted?")? {
         return Ok(());
     }
 
     app.client.login(app).await?;
 
-    let submission_id = submit_solution(app, &solution).await?;
+    let submission_id = submit_solution(app, &solution, &main_class).await?;
 
     let submission_url = make_submission_url(app, &submission_id)?;
     println!(
@@ -65,25 +96,105 @@ pub async fn submit(app: &App, args: &SubmitArgs) -> crate::Result<()> {
     Ok(())
 }
 
-fn make_submission_url(app: &App, submission_id: &str) -> crate::Result<String> {
-    Ok(format!(
-        "{}/{submission_id}",
-        app.config.try_kattisrc()?.kattis.submissions_url,
-    ))
+/// Runs the solution through its test cases. The submission is stopped if any
+/// of them fail, unless it is forced.
+fn test_before_submitting(app: &App, args: &SubmitArgs, solution: &Solution) -> crate::Result<()> {
+    println!();
+
+    let mut test_args = TestArgs::default();
+    test_args.path = solution.dir.clone();
+
+    let result = solution
+        .lang
+        .get_program_execution_commands(&solution.file)
+        .and_then(|execution_commands| run_tests(app, &test_args, solution, execution_commands));
+
+    println!();
+
+    match result {
+        Ok(summary) if summary.num_failed == 0 => Ok(()),
+        Ok(summary) if args.force => {
+            eprintln!(
+                "{}: {} test case(s) failed. Submitting anyway since --force was given.",
+                "Warning".bright_yellow(),
+                summary.num_failed
+            );
+            Ok(())
+        }
+        Ok(summary) => eyre::bail!(
+            "{} of {} test case(s) failed. Fix your solution or use --force to submit anyway.",
+            summary.num_failed,
+            summary.num_passed + summary.num_failed
+        ),
+        Err(e) if args.force => {
+            eprintln!(
+                "{}: Failed to test the solution: {e}. Submitting anyway since --force was given.",
+                "Warning".bright_yellow()
+            );
+            Ok(())
+        }
+        Err(e) => Err(e.wrap_err(
+            "Failed to test the solution. Fix the problem or use --force to submit anyway.",
+        )),
+    }
+}
+
+/// Finds the class Kattis should run the solution with. Kattis ignores it for
+/// languages that do not need one, so the file name is used for those.
+fn resolve_main_class(args: &SubmitArgs, solution: &Solution) -> crate::Result<String> {
+    if let Some(main_class) = args.main_class.as_deref().or(solution.lang.main_class()) {
+        return Ok(main_class.to_string());
+    }
+
+    let file_stem = solution
+        .file
+        .file_stem()
+        .unwrap_or_default()
+        .to_string_lossy()
+        .into_owned();
+
+    let kattis_language = solution.lang.kattis_name();
+
+    if !needs_main_class(kattis_language) {
+        return Ok(file_stem);
+    }
+
+    let source = fs::read_to_string(&solution.file).wrap_err("Failed to read solution file")?;
+
+    match detect_main_class(kattis_language, &source, &file_stem) {
+        Some(main_class) => Ok(main_class),
+        None => {
+            eprintln!(
+                "{}: Could not find the main class in {}, so {file_stem} is used. Use --main-class to choose another.",
+                "Warning".bright_yellow(),
+                solution.file.display()
+            );
+
+            Ok(file_stem)
+        }
+    }
 }
 
-async fn submit_solution(app: &App, solution: &Solution<'_>) -> crate::Result<String> {
+fn format_limits(metadata: &ProblemMetadata) -> Option<String> {
+    let time_limit = metadata
+        .time_limit
+        .map(|limit| format!("{}s", limit.as_secs_f64()));
+    let memory_limit = metadata.memory_limit.map(|mb| format!("{mb} MB"));
+
+    match (time_limit, memory_limit) {
+        (Some(time), Some(memory)) => Some(format!("{time}, {memory}")),
+        (time, memory) => time.or(memory),
+    }
+}
+
+async fn submit_solution(
+    app: &App,
+    solution: &Solution<'_>,
+    main_class: &str,
+) -> crate::Result<String> {
     let kattisrc = app.config.try_kattisrc()?;
     let file_name = resolve_and_get_file_name(&solution.file)?;
 
-    // This will work for the vast majority of cases, but it may fail in some
-    // instances. It has to be improved in the future if there is any problem.
-    // Kattis doesn't care about mainclass if the programming language doesn't
-    // need it (for example Python), so whatever we set doesn't matter. For the
-    // languages where it is required (for example Java), the file name is very
-    // likely to be the same as the main class name.
-    let main_class = file_name.split('.').next().unwrap_or_default().to_string();
-
     let file_bytes = fs::read(&solution.file).wrap_err("Failed to read solution file")?;
     let file_part = Part::bytes(file_bytes)
         .file_name(file_name)
@@ -91,9 +202,9 @@ async fn submit_solution(app: &App, solution: &Solution<'_>) -> crate::Result<St
 
     let form = Form::new()
         .text("problem", solution.id.clone())
-        .text("language", solution.lang.to_string())
+        .text("language", solution.lang.kattis_name().to_string())
         .part("sub_file[]", file_part)
-        .text("mainclass", main_class)
+        .text("mainclass", main_class.to_string())
         .text("submit_ctr", "2")
         .text("submit", "true")
         .text("script", "true");
@@ -313,7 +424,7 @@ impl TestCaseStatus {
                 .attr("title")
                 .unwrap_or_default()
                 .split(':')
-                .last()
+                .next_back()
                 .unwrap_or_default()
                 .trim();
 
@@ -0,0 +1,82 @@
+use std::fs;
+
+use colored::Colorize;
+use eyre::Context;
+
+use crate::App;
+
+pub async fn templates(app: &App) -> crate::Result<()> {
+    let templates_dir = app.config.templates_dir_path();
+
+    let mut templates = Vec::new();
+
+    let entries = if templates_dir.is_dir() {
+        fs::read_dir(&templates_dir)
+            .wrap_err_with(|| format!("Failed to read {}", templates_dir.display()))?
+            .collect()
+    } else {
+        Vec::new()
+    };
+
+    for entry in entries {
+        let entry =
+            entry.wrap_err_with(|| format!("Failed to read {}", templates_dir.display()))?;
+        let file_name = entry.file_name().to_string_lossy().into_owned();
+
+        if file_name.starts_with('.') {
+            continue;
+        }
+
+        if entry.path().is_dir() {
+            let is_default = app
+                .config
+                .languages
+                .iter()
+                .any(|lang| lang.default_template() == file_name);
+
+            templates.push((file_name, "Folder".to_string(), is_default));
+            continue;
+        }
+
+        let Some((name, ext)) = file_name.rsplit_once('.') else {
+            continue;
+        };
+
+        if let Some(lang) = app.config.lang_from_file_ext(ext) {
+            let is_default = lang.default_template() == name;
+            templates.push((name.to_string(), lang.to_string(), is_default));
+        }
+    }
+
+    if templates.is_empty() {
+        println!(
+            "No templates found. Create them in {}",
+            templates_dir.display()
+        );
+        return Ok(());
+    }
+
+    templates.sort();
+
+    let name_width = templates
+        .iter()
+        .map(|(name, _, _)| name.len())
+        .max()
+        .unwrap_or_default()
+        .max("Name".len());
+
+    println!(
+        "{:name_width$}  {}",
+        "Name".bright_cyan(),
+        "Language".bright_cyan()
+    );
+    for (name, lang, is_default) in templates {
+        if is_default {
+            println!("{name:name_width$}  {lang} {}", "(default)".bright_green());
+        } else {
+            println!("{name:name_width$}  {lang}");
+        }
+    }
+
+    Ok(())
+}
@@ -1,6 +1,12 @@
 use std::{
-    io::{stdout, Write},
+    collections::HashMap,
+    fs,
     path::Path,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        mpsc,
+    },
+    thread,
 };
 
 use colored::Colorize;
@@ -9,17 +15,18 @@ use notify::{event::ModifyKind, EventKind, RecommendedWatcher, RecursiveMode, Wa
 use regex::Regex;
 
 use crate::{
-    cli::TestArgs,
+    cli::{SaveOutput, TestArgs},
+    compile_cache::compile_with_cache,
     config::language::ExecuteProgramCommands,
-    solution::{get_test_cases, get_test_dir, Solution, SolutionOptions},
-    test_io::{run_compile_cmd, run_test},
+    problem::ProblemMetadata,
+    reporter::{create_reporter, Reporter, TestSummary},
+    solution::{find_test_dir, get_test_cases, Solution, SolutionOptions},
+    test_io::{run_test, FileTestCase, TestCaseError, TestCaseResult, TestSetup},
     utils::prompt_bool,
+    validator::{resolve_interactor, OutputValidator, OutputValidatorOptions},
     App,
 };
 
-pub const SUCCESS: &str = "✅";
-pub const FAILURE: &str = "❌";
-
 pub async fn test(app: &App, args: &TestArgs) -> crate::Result<()> {
     let solution = Solution::from_folder(
         app,
@@ -37,7 +44,9 @@ pub async fn test(app: &App, args: &TestArgs) -> crate::Result<()> {
             .lang
             .get_program_execution_commands(&solution.file)?;
 
-        run_tests(app, args, &solution, execution_commands)
+        run_tests(app, args, &solution, execution_commands)?;
+
+        Ok(())
     };
 
     if args.watch {
@@ -49,15 +58,15 @@ pub async fn test(app: &App, args: &TestArgs) -> crate::Result<()> {
     Ok(())
 }
 
-fn run_tests(
+/// Runs the solution through its test cases and reports the results as
+/// requested in the arguments.
+pub fn run_tests(
     app: &App,
     args: &TestArgs,
     solution: &Solution,
     execution_commands: ExecuteProgramCommands,
-) -> crate::Result<()> {
-    if let Some(compile_cmd) = execution_commands.compile_cmd() {
-        run_compile_cmd(app, compile_cmd)?;
-    }
+) -> crate::Result<TestSummary> {
+    compile_with_cache(app, &solution.file, &execution_commands, args.rebuild)?;
 
     let mut test_cases = get_test_cases(&solution.dir)?;
 
@@ -68,56 +77,234 @@ fn run_tests(
         test_cases.retain(|test_case| regex_filter.is_match(&test_case.name));
     }
 
-    let mut num_failed_tests = 0;
+    if !args.group.is_empty() {
+        test_cases.retain(|test_case| {
+            args.group
+                .iter()
+                .any(|group| is_in_group(test_case, group.trim_end_matches('/')))
+        });
+
+        eyre::ensure!(
+            !test_cases.is_empty(),
+            "No test cases found in the group(s): {}",
+            args.group.join(", ")
+        );
+    }
+
+    // The limits from Kattis are used unless others are given.
+    let metadata = ProblemMetadata::load(&solution.dir)?;
+    let limits = solution.resource_limits(
+        args.time_limit.or(metadata.time_limit),
+        args.memory_limit.or(metadata.memory_limit),
+    );
+    let jobs = resolve_number_of_jobs(args);
+    let validator = OutputValidator::for_solution_dir(
+        app,
+        &solution.dir,
+        OutputValidatorOptions {
+            validator_path: args.validator.as_deref(),
+            validator_flags: args.validator_flags.as_deref(),
+            float_tolerance: args.float_tolerance,
+        },
+    )?;
+    let interactor = resolve_interactor(app, &solution.dir, args.interactor.as_deref())?;
+    let mut reporter = create_reporter(app, args, &solution.id);
+
+    reporter.start(test_cases.len())?;
 
-    println!("Running {} tests\n", test_cases.len());
+    let setup = TestSetup {
+        run_cmd: execution_commands.run_cmd(),
+        limits,
+        validator: &validator,
+        interactor: interactor.as_ref(),
+    };
 
-    for test_case in &test_cases {
-        print!("test {} ... ", test_case.name);
-        stdout().flush().wrap_err("Failed to flush output")?;
+    let summary = run_test_cases(
+        app,
+        &setup,
+        &test_cases,
+        jobs,
+        args.save_output,
+        reporter.as_mut(),
+    )?;
 
-        let outcome = run_test(app, execution_commands.run_cmd(), test_case)?;
+    reporter.finish(&summary)?;
 
-        print!("{}", if outcome.is_ok() { SUCCESS } else { FAILURE });
+    Ok(summary)
+}
 
-        if args.time {
-            if let Ok(test_info) = &outcome {
-                print!(" in {:.2}s", test_info.running_time.as_secs_f64());
-            }
+/// Whether the test case is in the group or in one of its subgroups.
+fn is_in_group(test_case: &FileTestCase, group: &str) -> bool {
+    test_case.group.as_deref().is_some_and(|test_group| {
+        test_group == group
+            || test_group
+                .strip_prefix(group)
+                .is_some_and(|rest| rest.starts_with('/'))
+    })
+}
+
+/// Runs the test cases on a pool of `jobs` worker threads. Outcomes are
+/// reported in the same order as the test cases are given.
+fn run_test_cases(
+    app: &App,
+    setup: &TestSetup,
+    test_cases: &[FileTestCase],
+    jobs: usize,
+    save_output: Option<SaveOutput>,
+    reporter: &mut dyn Reporter,
+) -> crate::Result<TestSummary> {
+    let next_test_index = AtomicUsize::new(0);
+
+    thread::scope(|scope| {
+        let (tx, rx) = mpsc::channel();
+
+        for _ in 0..jobs {
+            let tx = tx.clone();
+            let next_test_index = &next_test_index;
+
+            scope.spawn(move || loop {
+                let index = next_test_index.fetch_add(1, Ordering::Relaxed);
+
+                let Some(test_case) = test_cases.get(index) else {
+                    break;
+                };
+
+                let outcome = run_test(app, setup, test_case);
+
+                // The receiver is gone if an error occurred, in which case the
+                // remaining tests should not be run.
+                if tx.send((index, outcome)).is_err() {
+                    break;
+                }
+            });
         }
 
-        if outcome.is_err() {
-            num_failed_tests += 1;
+        drop(tx);
+
+        // Test cases may finish out of order, so outcomes are buffered until
+        // it is their turn to be reported.
+        let mut finished_outcomes = HashMap::new();
+        let mut summary = TestSummary::default();
+
+        for (index, test_case) in test_cases.iter().enumerate() {
+            reporter.test_started(test_case)?;
+
+            let outcome = loop {
+                if let Some(outcome) = finished_outcomes.remove(&index) {
+                    break outcome;
+                }
+
+                let (finished_index, outcome) = rx.recv().wrap_err(
+                    "Test runner stopped unexpectedly. This is a bug, please report it!",
+                )?;
+
+                finished_outcomes.insert(finished_index, outcome);
+            };
+
+            let mut outcome = outcome?;
+
+            if let Some(save_output) = save_output {
+                save_test_output(test_case, &mut outcome, save_output)?;
+            }
+
+            summary.add(test_case, &outcome);
+            reporter.test_finished(test_case, &outcome)?;
         }
 
-        println!();
+        Ok(summary)
+    })
+}
 
-        if let Err(test_case_error) = outcome {
-            test_case_error.print(app);
+/// Writes the output of the solution next to the test case's input file. The
+/// files of tests that are not saved are removed such that no outdated output
+/// is left behind.
+fn save_test_output(
+    test_case: &FileTestCase,
+    outcome: &mut TestCaseResult,
+    save_output: SaveOutput,
+) -> crate::Result<()> {
+    let out_file = test_case.input_file.with_extension("out");
+    let err_file = test_case.input_file.with_extension("err");
+    let transcript_file = test_case.input_file.with_extension("transcript");
+
+    for file in [&out_file, &err_file, &transcript_file] {
+        if file.is_file() {
+            fs::remove_file(file)
+                .wrap_err_with(|| format!("Failed to remove old output file {}", file.display()))?;
         }
     }
 
-    let overall_outcome = if num_failed_tests == 0 {
-        "ok".bright_green()
-    } else {
-        "failed".bright_red()
+    let (stdout, stderr, transcript) = match &*outcome {
+        Ok(_) if save_output == SaveOutput::Failed => return Ok(()),
+        Ok(info) => (Some(&info.stdout), &info.stderr, None),
+        Err(TestCaseError::WrongAnswer { actual, stderr, .. }) => (Some(actual), stderr, None),
+        Err(
+            TestCaseError::RuntimeError { stdout, stderr, .. }
+            | TestCaseError::TimeLimitExceeded { stdout, stderr, .. }
+            | TestCaseError::MemoryLimitExceeded { stdout, stderr, .. },
+        ) => (Some(stdout), stderr, None),
+        Err(TestCaseError::WrongInteraction {
+            transcript, stderr, ..
+        }) => (None, stderr, Some(transcript)),
     };
-    let num_passed_tests = test_cases.len() - num_failed_tests;
 
-    println!(
-        "\nTest result: {overall_outcome}. {num_passed_tests} passed; {num_failed_tests} failed.",
-    );
+    let write_file = |path: &Path, contents: &str| {
+        fs::write(path, contents)
+            .wrap_err_with(|| format!("Failed to save output to {}", path.display()))
+    };
+
+    if let Some(stdout) = stdout {
+        // Wrong answers are stored without the final newline, so it is added
+        // back to make the file comparable with the .ans file.
+        let stdout = stdout.trim_end();
+        let stdout = if stdout.is_empty() {
+            String::new()
+        } else {
+            format!("{stdout}\n")
+        };
+
+        write_file(&out_file, &stdout)?;
+    }
+
+    write_file(&err_file, stderr)?;
+
+    if let Some(transcript) = transcript {
+        write_file(&transcript_file, transcript)?;
+    }
+
+    // Outputs that are too large to be shown point to the saved file instead
+    // of a temporary copy.
+    if let Err(TestCaseError::WrongAnswer { saved_output, .. }) = outcome {
+        *saved_output = Some(out_file);
+    }
 
     Ok(())
 }
 
+/// Parallel test runs skew running times, so tests are run one at a time when
+/// timing them unless explicitly requested otherwise.
+fn resolve_number_of_jobs(args: &TestArgs) -> usize {
+    let jobs = args.jobs as usize;
+
+    if jobs > 1 && args.time && !args.parallel_timing {
+        eprintln!(
+            "{}: running tests one at a time to keep time measurements accurate. Use --parallel-timing to override this.",
+            "Note".bright_cyan()
+        );
+
+        return 1;
+    }
+
+    jobs
+}
+
 async fn fetch_tests_if_needed(
     app: &App,
     args: &TestArgs,
     problem_id: &str,
     solution_dir: impl AsRef<Path>,
 ) -> crate::Result<()> {
-    let test_dir = get_test_dir(&solution_dir);
+    let test_dir = find_test_dir(&solution_dir);
 
     if test_dir.exists() {
         return Ok(());
@@ -0,0 +1,146 @@
+use std::{
+    collections::hash_map::DefaultHasher,
+    env, fs,
+    hash::{Hash, Hasher},
+    io,
+    path::{Path, PathBuf},
+};
+
+use colored::Colorize;
+use eyre::Context;
+
+use crate::{config::language::ExecuteProgramCommands, test_io::run_compile_cmd, App};
+
+/// Compiles a program unless it was already compiled from the same source code
+/// with the same compile command and compiler. The cache is ignored if
+/// `rebuild` is set.
+///
+/// The cache stores a hash of the source code, the compile command and the
+/// modification time of the compiler for each source file in kitty's config
+/// folder.
+pub fn compile_with_cache(
+    app: &App,
+    source_file: impl AsRef<Path>,
+    exec_cmds: &ExecuteProgramCommands,
+    rebuild: bool,
+) -> crate::Result<()> {
+    let Some(compile_cmd) = exec_cmds.compile_cmd() else {
+        return Ok(());
+    };
+
+    let source_file = source_file.as_ref();
+    let entry_path = cache_entry_path(app, source_file)?;
+    let key = cache_key(source_file, compile_cmd)?;
+
+    if !rebuild && is_up_to_date(&entry_path, &key, exec_cmds.run_cmd()) {
+        if app.args.verbose {
+            eprintln!(
+                "{} compilation since {} has not changed\n",
+                "Skipping".bright_cyan(),
+                source_file.display()
+            );
+        }
+
+        return Ok(());
+    }
+
+    // The old entry is removed first such that a failed compilation is never
+    // mistaken for an up-to-date program.
+    if let Err(e) = fs::remove_file(&entry_path) {
+        if e.kind() != io::ErrorKind::NotFound {
+            return Err(e).wrap_err("Failed to remove outdated compilation cache entry");
+        }
+    }
+
+    run_compile_cmd(app, compile_cmd)?;
+
+    if let Some(cache_dir) = entry_path.parent() {
+        fs::create_dir_all(cache_dir).wrap_err("Failed to create compilation cache folder")?;
+    }
+
+    fs::write(&entry_path, key).wrap_err("Failed to write compilation cache entry")
+}
+
+/// Removes the cache entry for a source file such that it is compiled again the
+/// next time it is run.
+pub fn forget(app: &App, source_file: impl AsRef<Path>) -> crate::Result<()> {
+    let entry_path = cache_entry_path(app, source_file.as_ref())?;
+
+    match fs::remove_file(entry_path) {
+        Err(e) if e.kind() != io::ErrorKind::NotFound => {
+            Err(e).wrap_err("Failed to remove compilation cache entry")
+        }
+        _ => Ok(()),
+    }
+}
+
+fn is_up_to_date(entry_path: &Path, key: &str, run_cmd: &[String]) -> bool {
+    let Ok(cached_key) = fs::read_to_string(entry_path) else {
+        return false;
+    };
+
+    // If the run command points directly at the compiled program, it must
+    // still exist. Other compiled files, such as Java's class files, are not
+    // checked.
+    let program_exists = run_cmd.first().map_or(true, |program| {
+        let program = Path::new(program);
+        program.components().count() <= 1 || program.exists()
+    });
+
+    cached_key == key && program_exists
+}
+
+fn cache_entry_path(app: &App, source_file: &Path) -> crate::Result<PathBuf> {
+    let source_file = source_file
+        .canonicalize()
+        .wrap_err("Failed to find the source file")?;
+
+    let mut hasher = DefaultHasher::new();
+    source_file.hash(&mut hasher);
+
+    Ok(app
+        .config
+        .compile_cache_dir_path()
+        .join(format!("{:016x}", hasher.finish())))
+}
+
+fn cache_key(source_file: &Path, compile_cmd: &[String]) -> crate::Result<String> {
+    let source = fs::read(source_file).wrap_err("Failed to read the source file")?;
+
+    let compiler_modified = compile_cmd
+        .first()
+        .and_then(|compiler| find_program(compiler))
+        .and_then(|compiler| compiler.metadata().ok())
+        .and_then(|metadata| metadata.modified().ok());
+
+    let mut hasher = DefaultHasher::new();
+    env!("CARGO_PKG_VERSION").hash(&mut hasher);
+    source.hash(&mut hasher);
+    compile_cmd.hash(&mut hasher);
+    compiler_modified.hash(&mut hasher);
+
+    Ok(format!("{:016x}", hasher.finish()))
+}
+
+/// Finds the file that would be run for the given program, searching the
+/// folders in PATH if it is not a path itself.
+fn find_program(program: &str) -> Option<PathBuf> {
+    let program = Path::new(program);
+
+    if program.components().count() > 1 {
+        return Some(program.to_path_buf());
+    }
+
+    let path = env::var_os("PATH")?;
+
+    env::split_paths(&path)
+        .map(|dir| dir.join(program))
+        .find_map(|candidate| {
+            if candidate.is_file() {
+                return Some(candidate);
+            }
+
+            let with_exe_extension = candidate.with_extension(env::consts::EXE_EXTENSION);
+            with_exe_extension.is_file().then_some(with_exe_extension)
+        })
+}
@@ -1,8 +1,73 @@
-use std::{fmt, path::Path};
+use std::{
+    fmt, fs,
+    path::{Path, PathBuf},
+};
 
 use eyre::Context;
 
-use crate::config::prepare_cmd;
+use crate::{config::prepare_cmd, utils::get_full_path};
+
+/// The name of the template used for languages that do not configure one.
+pub const DEFAULT_TEMPLATE_NAME: &str = "template";
+
+/// The names of the languages Kattis accepts, exactly as in the language
+/// dropdown at https://open.kattis.com/submit.
+pub const KATTIS_LANGUAGES: &[&str] = &[
+    "Ada",
+    "Algol 60",
+    "Algol 68",
+    "APL",
+    "Bash",
+    "BASIC",
+    "Befunge",
+    "BQN",
+    "C",
+    "C#",
+    "C++",
+    "COBOL",
+    "Common Lisp",
+    "Crystal",
+    "D",
+    "Dart",
+    "Elixir",
+    "Erlang",
+    "F#",
+    "Forth",
+    "Fortran",
+    "Gerbil",
+    "Go",
+    "Groovy",
+    "Haskell",
+    "Java",
+    "JavaScript (Node.js)",
+    "JavaScript (SpiderMonkey)",
+    "Julia",
+    "Kotlin",
+    "Lua",
+    "Modula-2",
+    "Nim",
+    "Objective-C",
+    "OCaml",
+    "Octave",
+    "Odin",
+    "Pascal",
+    "Perl",
+    "PHP",
+    "Prolog",
+    "Python 2",
+    "Python 3",
+    "Racket",
+    "Ruby",
+    "Rust",
+    "Scala",
+    "Simula 67",
+    "Smalltalk",
+    "SNOBOL",
+    "Swift",
+    "TypeScript",
+    "Visual Basic",
+    "Zig",
+];
 
 #[derive(Debug)]
 pub struct Language {
@@ -10,6 +75,12 @@ pub struct Language {
     file_ext: String,
     run_cmd: String,
     compile_cmd: Option<String>,
+    time_limit_multiplier: Option<f64>,
+    build_dir: Option<String>,
+    artifacts: Vec<String>,
+    default_template: Option<String>,
+    kattis_language: Option<String>,
+    main_class: Option<String>,
 }
 
 impl Language {
@@ -25,41 +96,221 @@ impl Language {
             file_ext,
             run_cmd,
             compile_cmd,
+            time_limit_multiplier: None,
+            build_dir: None,
+            artifacts: Vec::new(),
+            default_template: None,
+            kattis_language: None,
+            main_class: None,
         }
     }
 
+    pub fn with_time_limit_multiplier(mut self, multiplier: Option<f64>) -> Self {
+        self.time_limit_multiplier = multiplier;
+        self
+    }
+
+    pub fn with_build_dir(mut self, build_dir: Option<String>) -> Self {
+        self.build_dir = build_dir;
+        self
+    }
+
+    pub fn with_artifacts(mut self, artifacts: Vec<String>) -> Self {
+        self.artifacts = artifacts;
+        self
+    }
+
+    pub fn with_default_template(mut self, default_template: Option<String>) -> Self {
+        self.default_template = default_template;
+        self
+    }
+
+    pub fn with_kattis_language(mut self, kattis_language: Option<String>) -> Self {
+        self.kattis_language = kattis_language;
+        self
+    }
+
+    pub fn with_main_class(mut self, main_class: Option<String>) -> Self {
+        self.main_class = main_class;
+        self
+    }
+
     pub fn file_ext(&self) -> &str {
         &self.file_ext
     }
 
+    /// The factor to scale time limits by when running programs in this
+    /// language. Defaults to 1.
+    pub fn time_limit_multiplier(&self) -> f64 {
+        self.time_limit_multiplier.unwrap_or(1.0)
+    }
+
+    /// The name Kattis knows the language by. Defaults to the display name.
+    pub fn kattis_name(&self) -> &str {
+        self.kattis_language.as_deref().unwrap_or(&self.name)
+    }
+
+    /// The main class to submit solutions with, if one is configured instead of
+    /// detecting it from the source code.
+    pub fn main_class(&self) -> Option<&str> {
+        self.main_class.as_deref()
+    }
+
+    /// Checks that Kattis accepts submissions in the language under its
+    /// Kattis name.
+    pub fn ensure_known_by_kattis(&self) -> crate::Result<()> {
+        let kattis_name = self.kattis_name();
+
+        if KATTIS_LANGUAGES.contains(&kattis_name) {
+            return Ok(());
+        }
+
+        let setting = if self.kattis_language.is_some() {
+            format!("The 'kattis_language' of {}", self.name)
+        } else {
+            format!("The language name '{}'", self.name)
+        };
+
+        match suggest_kattis_language(kattis_name) {
+            Some(suggestion) => eyre::bail!(
+                "{setting} is not a language Kattis knows. Did you mean '{suggestion}'? Set 'kattis_language' for the language in your config file to the name Kattis uses."
+            ),
+            None => eyre::bail!(
+                "{setting} is not a language Kattis knows. Set 'kattis_language' for the language in your config file to one of: {}",
+                KATTIS_LANGUAGES.join(", ")
+            ),
+        }
+    }
+
+    /// The name of the template that `kitty get` uses for this language when no
+    /// other template is chosen. Defaults to "template".
+    pub fn default_template(&self) -> &str {
+        self.default_template
+            .as_deref()
+            .unwrap_or(DEFAULT_TEMPLATE_NAME)
+    }
+
+    /// The folder that compiled programs are placed in for the given source
+    /// file, if one is configured. Relative paths are relative to the folder
+    /// containing the source file.
+    pub fn build_dir(&self, file_path: impl AsRef<Path>) -> crate::Result<Option<PathBuf>> {
+        let Some(build_dir) = &self.build_dir else {
+            return Ok(None);
+        };
+
+        let file_path = get_full_path(file_path)?;
+        let source_dir = file_path.parent().ok_or_else(|| {
+            eyre::eyre!("Could not find parent of path '{}'", file_path.display())
+        })?;
+
+        Ok(Some(source_dir.join(build_dir)))
+    }
+
+    /// Prepares the commands for compiling and running the given source file.
+    /// The build folder is created if the program needs to be compiled.
     pub fn get_program_execution_commands(
         &self,
         file_path: impl AsRef<Path>,
     ) -> crate::Result<ExecuteProgramCommands> {
         let file_path = file_path.as_ref();
+        let compile_cmd = self.get_compile_cmd(file_path)?;
+
+        if compile_cmd.is_some() {
+            if let Some(build_dir) = self.build_dir(file_path)? {
+                fs::create_dir_all(&build_dir).wrap_err_with(|| {
+                    format!("Failed to create build folder '{}'", build_dir.display())
+                })?;
+            }
+        }
 
         Ok(ExecuteProgramCommands {
             run_cmd: self.get_run_cmd(file_path)?,
-            compile_cmd: self.get_compile_cmd(file_path)?,
+            compile_cmd,
         })
     }
 
+    /// The paths of the files produced when compiling the given source file.
+    /// Paths may contain `*` and `?` wildcards in their last component and are
+    /// relative to the folder containing the source file. If no artifacts are
+    /// configured, compiled languages produce just the executable.
+    pub fn get_artifacts(&self, file_path: impl AsRef<Path>) -> crate::Result<Vec<PathBuf>> {
+        let file_path = get_full_path(file_path)?;
+        let source_dir = file_path.parent().ok_or_else(|| {
+            eyre::eyre!("Could not find parent of path '{}'", file_path.display())
+        })?;
+        let build_dir = self.build_dir(&file_path)?;
+
+        let patterns = match (&self.compile_cmd, self.artifacts.is_empty()) {
+            (None, true) => return Ok(Vec::new()),
+            (Some(_), true) => vec!["$EXE_PATH".to_string()],
+            (_, false) => self.artifacts.clone(),
+        };
+
+        let mut artifacts = Vec::new();
+
+        for pattern in patterns {
+            let paths = prepare_cmd(&pattern, &file_path, build_dir.as_deref())
+                .wrap_err_with(|| format!("Failed to parse the artifact '{pattern}'"))?;
+
+            artifacts.extend(paths.into_iter().map(|path| source_dir.join(path)));
+        }
+
+        Ok(artifacts)
+    }
+
     pub fn get_run_cmd(&self, file_path: impl AsRef<Path>) -> crate::Result<Vec<String>> {
-        prepare_cmd(&self.run_cmd, file_path).wrap_err("Failed to parse the run command")
+        let file_path = file_path.as_ref();
+
+        prepare_cmd(
+            &self.run_cmd,
+            file_path,
+            self.build_dir(file_path)?.as_deref(),
+        )
+        .wrap_err("Failed to parse the run command")
     }
 
     pub fn get_compile_cmd(
         &self,
         file_path: impl AsRef<Path>,
     ) -> crate::Result<Option<Vec<String>>> {
+        let file_path = file_path.as_ref();
+        let build_dir = self.build_dir(file_path)?;
+
         self.compile_cmd
             .as_ref()
-            .map(|cmd| prepare_cmd(cmd, file_path))
+            .map(|cmd| prepare_cmd(cmd, file_path, build_dir.as_deref()))
             .transpose()
             .wrap_err("Failed to parse the compile command")
     }
 }
 
+/// Finds the Kattis language that a name most likely refers to, such as C++
+/// for "c++" or "C++ (clang)".
+fn suggest_kattis_language(name: &str) -> Option<&'static str> {
+    let name = name.to_lowercase();
+
+    KATTIS_LANGUAGES
+        .iter()
+        .copied()
+        .filter(|kattis_name| {
+            let kattis_name = kattis_name.to_lowercase();
+
+            // Single letters such as C are contained in too many names to be
+            // useful suggestions.
+            let (shorter, longer) = if name.len() < kattis_name.len() {
+                (&name, &kattis_name)
+            } else {
+                (&kattis_name, &name)
+            };
+
+            name == kattis_name || (shorter.len() > 1 && longer.contains(shorter.as_str()))
+        })
+        .max_by_key(|kattis_name| {
+            let is_exact = kattis_name.to_lowercase() == name;
+            (is_exact, kattis_name.len())
+        })
+}
+
 impl fmt::Display for Language {
     fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
         fmt.write_str(&self.name)
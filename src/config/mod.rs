@@ -19,6 +19,14 @@ pub struct Config {
     pub config_dir: PathBuf,
     pub default_language: Option<String>,
     pub languages: Vec<Language>,
+    pub submit: SubmitSettings,
+}
+
+/// The settings under `submit` in the config file.
+#[derive(Debug, Default)]
+pub struct SubmitSettings {
+    /// Whether solutions must pass their test cases before they are submitted.
+    pub require_tests: bool,
 }
 
 impl Config {
@@ -71,6 +79,17 @@ impl Config {
         Self::templates_dir_path_with_dir(&self.config_dir)
     }
 
+    /// The path of the file template with the given name for the language,
+    /// such as `templates/fastio.cpp`.
+    pub fn template_file_path(&self, name: &str, lang: &Language) -> PathBuf {
+        self.templates_dir_path()
+            .join(format!("{name}.{}", lang.file_ext()))
+    }
+
+    pub fn compile_cache_dir_path(&self) -> PathBuf {
+        self.config_dir.join("cache").join("compile")
+    }
+
     pub fn kattisrc_path_with_dir(dir: impl AsRef<Path>) -> PathBuf {
         dir.as_ref().join(".kattisrc")
     }
@@ -111,7 +130,14 @@ impl Config {
     }
 }
 
-pub fn prepare_cmd(cmd: &str, file_path: impl AsRef<Path>) -> crate::Result<Vec<String>> {
+/// Fills in the variables of a command for the given source file. When a build
+/// folder is given, compiled programs are placed there instead of next to the
+/// source file.
+pub fn prepare_cmd(
+    cmd: &str,
+    file_path: impl AsRef<Path>,
+    build_dir: Option<&Path>,
+) -> crate::Result<Vec<String>> {
     fn path_to_str(path: impl AsRef<Path>) -> crate::Result<String> {
         path.as_ref()
             .to_str()
@@ -125,7 +151,10 @@ pub fn prepare_cmd(cmd: &str, file_path: impl AsRef<Path>) -> crate::Result<Vec<
         .parent()
         .ok_or_else(|| eyre::eyre!("Could not find parent of path '{}'", file_path.display()))?;
 
-    let exe_path = file_path.with_extension(EXE_EXTENSION);
+    let build_dir = build_dir.unwrap_or(dir_path);
+    let exe_path = build_dir
+        .join(file_path.file_name().unwrap_or_default())
+        .with_extension(EXE_EXTENSION);
     let file_name_no_ext = file_path.file_stem().unwrap().to_str().unwrap();
 
     let parts = shlex::split(cmd)
@@ -136,6 +165,7 @@ pub fn prepare_cmd(cmd: &str, file_path: impl AsRef<Path>) -> crate::Result<Vec<
                 .replace("$SRC_PATH", &path_to_str(&file_path)?)
                 .replace("$SRC_FILE_NAME_NO_EXT", file_name_no_ext)
                 .replace("$DIR_PATH", &path_to_str(dir_path)?)
+                .replace("$BUILD_DIR", &path_to_str(build_dir)?)
                 .replace("$EXE_PATH", &path_to_str(&exe_path)?);
 
             Ok(populated)
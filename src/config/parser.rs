@@ -1,6 +1,9 @@
 use std::{fs, path::Path};
 
-use crate::{config::language::Language, config::Config};
+use crate::{
+    config::language::Language,
+    config::{Config, SubmitSettings},
+};
 use eyre::Context;
 use yaml_rust::{Yaml, YamlLoader};
 
@@ -31,21 +34,39 @@ pub fn parse_config_from_yaml(yaml_str: &str) -> crate::Result<Config> {
     };
 
     let default_language = doc["default_language"].as_str().map(str::to_lowercase);
+    let build_dir = get_string_value("build_directory", doc);
     let languages = doc["languages"]
         .as_vec()
-        .map(|v| v.iter().map(lang_from_yml).collect::<Result<Vec<_>, _>>())
+        .map(|v| {
+            v.iter()
+                .map(|lang_block| lang_from_yml(lang_block, build_dir.as_deref()))
+                .collect::<Result<Vec<_>, _>>()
+        })
         .unwrap_or_else(|| Ok(Vec::new()))?;
 
+    let submit = submit_settings_from_yml(&doc["submit"])?;
+
     let config = Config {
         default_language,
         languages,
+        submit,
         ..Default::default()
     };
 
     Ok(config)
 }
 
-fn lang_from_yml(lang_block: &Yaml) -> crate::Result<Language> {
+fn submit_settings_from_yml(submit_block: &Yaml) -> crate::Result<SubmitSettings> {
+    let require_tests = match &submit_block["require_tests"] {
+        Yaml::BadValue | Yaml::Null => false,
+        Yaml::Boolean(require_tests) => *require_tests,
+        _ => eyre::bail!("The 'require_tests' setting under 'submit' must be true or false"),
+    };
+
+    Ok(SubmitSettings { require_tests })
+}
+
+fn lang_from_yml(lang_block: &Yaml, default_build_dir: Option<&str>) -> crate::Result<Language> {
     fn map_err_with_name(name: &str, err: eyre::Report) -> eyre::Report {
         eyre::eyre!("Failed to read language configuration for '{name}': {err}")
     }
@@ -57,8 +78,30 @@ fn lang_from_yml(lang_block: &Yaml) -> crate::Result<Language> {
     let run_cmd =
         get_value_else_err("run_command", lang_block).map_err(|e| map_err_with_name(&name, e))?;
     let compile_cmd = get_string_value("compile_command", lang_block);
+    let build_dir = get_string_value("build_directory", lang_block)
+        .or_else(|| default_build_dir.map(str::to_string));
+    let artifacts =
+        get_string_list_value("artifacts", lang_block).map_err(|e| map_err_with_name(&name, e))?;
+    let default_template = get_string_value("default_template", lang_block);
+    let kattis_language = get_string_value("kattis_language", lang_block);
+    let main_class = get_string_value("main_class", lang_block);
+    let time_limit_multiplier = get_f64_value("time_limit_multiplier", lang_block)
+        .map_err(|e| map_err_with_name(&name, e))?;
+
+    if time_limit_multiplier.is_some_and(|m| !m.is_finite() || m <= 0.0) {
+        return Err(map_err_with_name(
+            &name,
+            eyre::eyre!("The 'time_limit_multiplier' field must be a positive number"),
+        ));
+    }
 
-    Ok(Language::new(name, file_ext, run_cmd, compile_cmd))
+    Ok(Language::new(name, file_ext, run_cmd, compile_cmd)
+        .with_time_limit_multiplier(time_limit_multiplier)
+        .with_build_dir(build_dir)
+        .with_artifacts(artifacts)
+        .with_default_template(default_template)
+        .with_kattis_language(kattis_language)
+        .with_main_class(main_class))
 }
 
 fn get_value_else_err(key: &str, doc: &Yaml) -> crate::Result<String> {
@@ -70,6 +113,35 @@ fn get_string_value(key: &str, doc: &Yaml) -> Option<String> {
     get_value(key, doc).and_then(|y| y.into_string())
 }
 
+fn get_string_list_value(key: &str, doc: &Yaml) -> crate::Result<Vec<String>> {
+    match get_value(key, doc) {
+        None | Some(Yaml::BadValue) => Ok(Vec::new()),
+        Some(Yaml::Array(values)) => values
+            .into_iter()
+            .map(|value| {
+                value
+                    .into_string()
+                    .ok_or_else(|| eyre::eyre!("The '{key}' field must be a list of strings"))
+            })
+            .collect(),
+        Some(value) => value
+            .into_string()
+            .map(|value| vec![value])
+            .ok_or_else(|| eyre::eyre!("The '{key}' field must be a list of strings")),
+    }
+}
+
+fn get_f64_value(key: &str, doc: &Yaml) -> crate::Result<Option<f64>> {
+    match get_value(key, doc) {
+        None | Some(Yaml::BadValue) => Ok(None),
+        Some(Yaml::Integer(i)) => Ok(Some(i as f64)),
+        Some(value) => value
+            .as_f64()
+            .map(Some)
+            .ok_or_else(|| eyre::eyre!("The '{key}' field must be a number")),
+    }
+}
+
 fn get_value(key: &str, doc: &Yaml) -> Option<Yaml> {
     let platform_value = &doc[PLATFORM_KEY][key];
 
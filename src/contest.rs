@@ -0,0 +1,143 @@
+use eyre::Context;
+use regex::Regex;
+use scraper::{ElementRef, Html, Selector};
+
+use crate::App;
+
+/// A contest on Kattis.
+#[derive(Debug)]
+pub struct Contest {
+    /// The URL of the Kattis instance hosting the contest, such as
+    /// `https://open.kattis.com`.
+    pub host_url: String,
+    pub id: String,
+}
+
+impl Contest {
+    /// Reads a contest from either its URL or its ID. Contests given by ID are
+    /// assumed to be on the host in your .kattisrc file.
+    pub fn parse(app: &App, contest: &str) -> crate::Result<Self> {
+        let url_regex = Regex::new(r"^(https?://[^/]+)/contests/([\w.-]+)").unwrap();
+
+        if let Some(captures) = url_regex.captures(contest) {
+            return Ok(Self {
+                host_url: captures[1].to_string(),
+                id: captures[2].to_string(),
+            });
+        }
+
+        eyre::ensure!(
+            Regex::new(r"^[\w.-]+$").unwrap().is_match(contest),
+            "'{contest}' is neither a contest URL nor a contest ID"
+        );
+
+        let host_name = &app.config.try_kattisrc()?.kattis.host_name;
+
+        Ok(Self {
+            host_url: format!("https://{host_name}"),
+            id: contest.to_string(),
+        })
+    }
+
+    pub fn problems_url(&self) -> String {
+        format!("{}/contests/{}/problems", self.host_url, self.id)
+    }
+}
+
+/// A problem in a contest along with the letter it is given in the contest.
+#[derive(Debug)]
+pub struct ContestProblem {
+    pub letter: String,
+    pub id: String,
+}
+
+/// Finds the problems in the contest. You must be logged in to see the
+/// problems of contests that are not public.
+pub async fn fetch_contest_problems(
+    app: &App,
+    contest: &Contest,
+) -> crate::Result<Vec<ContestProblem>> {
+    let response = app
+        .client
+        .get(contest.problems_url())
+        .send()
+        .await
+        .wrap_err("Failed to send request to Kattis")?;
+
+    let status = response.status();
+    eyre::ensure!(
+        status.is_success(),
+        "Failed to get contest '{}' from Kattis (http status code: {status})",
+        contest.id
+    );
+
+    let page = response
+        .text()
+        .await
+        .wrap_err("Failed to read contest page from Kattis")?;
+
+    let problems = parse_contest_problems(&page);
+
+    eyre::ensure!(
+        !problems.is_empty(),
+        "Could not find any problems in contest '{}'. Has it started?",
+        contest.id
+    );
+
+    Ok(problems)
+}
+
+/// Reads the problems from the table on a contest's problem page. Problems
+/// are given letters in the order they are listed if the table has none.
+fn parse_contest_problems(page_html: &str) -> Vec<ContestProblem> {
+    let page = Html::parse_document(page_html);
+    let row_selector = Selector::parse("table tr").unwrap();
+    let link_selector = Selector::parse("a[href]").unwrap();
+    let cell_selector = Selector::parse("td, th").unwrap();
+    let problem_link = Regex::new(r"/problems/([\w.]+)/?$").unwrap();
+    let letter = Regex::new(r"^[A-Z]{1,2}$").unwrap();
+
+    let mut problems: Vec<ContestProblem> = Vec::new();
+
+    for row in page.select(&row_selector) {
+        let id = row
+            .select(&link_selector)
+            .filter_map(|link| link.value().attr("href"))
+            .find_map(|href| problem_link.captures(href))
+            .map(|captures| captures[1].to_string());
+
+        let Some(id) = id else {
+            continue;
+        };
+
+        if problems.iter().any(|problem| problem.id == id) {
+            continue;
+        }
+
+        // The first column of the table holds the letters.
+        let letter = row
+            .select(&cell_selector)
+            .next()
+            .map(cell_text)
+            .filter(|text| letter.is_match(text))
+            .unwrap_or_else(|| letter_from_index(problems.len()));
+
+        problems.push(ContestProblem { letter, id });
+    }
+
+    problems
+}
+
+fn cell_text(cell: ElementRef) -> String {
+    cell.text().collect::<String>().trim().to_string()
+}
+
+/// Turns 0, 1, ..., 25, 26, ... into A, B, ..., Z, AA, ...
+fn letter_from_index(index: usize) -> String {
+    let letter = |i: usize| char::from(b'A' + (i % 26) as u8);
+
+    match index / 26 {
+        0 => letter(index).to_string(),
+        n => format!("{}{}", letter(n - 1), letter(index)),
+    }
+}
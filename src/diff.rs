@@ -0,0 +1,240 @@
+use std::{collections::BTreeSet, fmt};
+
+use colored::Colorize;
+
+/// Outputs with more lines than this are shown as a diff instead of in full.
+const LARGE_OUTPUT_LINES: usize = 30;
+/// Outputs with more bytes than this are shown as a diff instead of in full.
+const LARGE_OUTPUT_BYTES: usize = 4096;
+/// The number of unchanged lines shown around each differing line.
+const CONTEXT_LINES: usize = 2;
+/// At most this many differing lines are shown.
+const MAX_SHOWN_DIFFERENCES: usize = 5;
+/// Longer lines are cut down to a window around the first difference.
+const MAX_LINE_WIDTH: usize = 120;
+
+/// Whether the outputs are too large to be shown in full.
+pub fn is_large_output(expected: &str, actual: &str) -> bool {
+    [expected, actual].iter().any(|output| {
+        output.len() > LARGE_OUTPUT_BYTES || output.lines().count() > LARGE_OUTPUT_LINES
+    })
+}
+
+/// The position of the first character that differs between two outputs. Both
+/// are 1-indexed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Mismatch {
+    pub line: usize,
+    pub column: usize,
+}
+
+pub fn first_mismatch(expected: &str, actual: &str) -> Option<Mismatch> {
+    let expected_lines = expected.lines().collect::<Vec<_>>();
+    let actual_lines = actual.lines().collect::<Vec<_>>();
+
+    let index = differing_lines(&expected_lines, &actual_lines).next()?;
+    let column = first_differing_column(
+        expected_lines.get(index).copied().unwrap_or_default(),
+        actual_lines.get(index).copied().unwrap_or_default(),
+    );
+
+    Some(Mismatch {
+        line: index + 1,
+        column: column + 1,
+    })
+}
+
+/// Shows the first differing lines of two outputs along with a few lines of
+/// context. Lines are compared by their position in the outputs, and the
+/// differing tokens within a line are highlighted.
+pub struct Diff<'a> {
+    expected: &'a str,
+    actual: &'a str,
+}
+
+impl<'a> Diff<'a> {
+    pub fn new(expected: &'a str, actual: &'a str) -> Self {
+        Self { expected, actual }
+    }
+}
+
+impl fmt::Display for Diff<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let expected_lines = self.expected.lines().collect::<Vec<_>>();
+        let actual_lines = self.actual.lines().collect::<Vec<_>>();
+        let num_lines = expected_lines.len().max(actual_lines.len());
+
+        let differing = differing_lines(&expected_lines, &actual_lines).collect::<Vec<_>>();
+
+        let Some(mismatch) = first_mismatch(self.expected, self.actual) else {
+            return writeln!(
+                f,
+                "The outputs only differ in whitespace at the ends of lines."
+            );
+        };
+
+        writeln!(
+            f,
+            "{} at line {}, column {} ({} of {} lines differ):",
+            "First difference".underline(),
+            mismatch.line,
+            mismatch.column,
+            differing.len(),
+            num_lines,
+        )?;
+
+        let shown = &differing[..differing.len().min(MAX_SHOWN_DIFFERENCES)];
+        let last_line = num_lines - 1;
+        let shown_lines = shown
+            .iter()
+            .flat_map(|&i| i.saturating_sub(CONTEXT_LINES)..=(i + CONTEXT_LINES).min(last_line))
+            // Differing lines beyond the shown ones are not used as context.
+            .filter(|i| shown.contains(i) || !differing.contains(i))
+            .collect::<BTreeSet<_>>();
+
+        let number_width = num_lines.to_string().len();
+        let mut previous_line = None;
+
+        for &i in &shown_lines {
+            if previous_line.is_some_and(|previous| previous + 1 != i) {
+                writeln!(f, "  {:>number_width$} |", "...")?;
+            }
+
+            previous_line = Some(i);
+
+            let expected_line = expected_lines.get(i).copied();
+            let actual_line = actual_lines.get(i).copied();
+
+            if !shown.contains(&i) {
+                let line = expected_line.unwrap_or_default();
+                writeln!(f, "  {:>number_width$} | {}", i + 1, cut_to_width(line, 0))?;
+                continue;
+            }
+
+            let column = first_differing_column(
+                expected_line.unwrap_or_default(),
+                actual_line.unwrap_or_default(),
+            );
+
+            let expected_shown = expected_line.map(|line| cut_to_width(line, column));
+            let actual_shown = actual_line.map(|line| cut_to_width(line, column));
+
+            match &expected_shown {
+                Some(line) => writeln!(
+                    f,
+                    "{} {:>number_width$} | {}",
+                    "-".bright_red(),
+                    i + 1,
+                    highlight(line, actual_shown.as_deref().unwrap_or_default(), false)
+                )?,
+                None => writeln!(
+                    f,
+                    "{} {:>number_width$} | {}",
+                    "-".bright_red(),
+                    i + 1,
+                    "(no more lines expected)".italic()
+                )?,
+            }
+
+            match &actual_shown {
+                Some(line) => writeln!(
+                    f,
+                    "{} {:>number_width$} | {}",
+                    "+".bright_green(),
+                    i + 1,
+                    highlight(line, expected_shown.as_deref().unwrap_or_default(), true)
+                )?,
+                None => writeln!(
+                    f,
+                    "{} {:>number_width$} | {}",
+                    "+".bright_green(),
+                    i + 1,
+                    "(your output ended)".italic()
+                )?,
+            }
+        }
+
+        let num_hidden = differing.len() - shown.len();
+
+        if num_hidden > 0 {
+            writeln!(f, "... and {num_hidden} more differing lines")?;
+        }
+
+        Ok(())
+    }
+}
+
+fn differing_lines<'a>(
+    expected_lines: &'a [&str],
+    actual_lines: &'a [&str],
+) -> impl Iterator<Item = usize> + 'a {
+    let num_lines = expected_lines.len().max(actual_lines.len());
+
+    (0..num_lines).filter(|&i| {
+        let expected = expected_lines.get(i).map(|line| line.trim_end());
+        let actual = actual_lines.get(i).map(|line| line.trim_end());
+        expected != actual
+    })
+}
+
+/// The 0-indexed position of the first character that differs.
+fn first_differing_column(expected: &str, actual: &str) -> usize {
+    let mut expected_chars = expected.trim_end().chars();
+    let mut actual_chars = actual.trim_end().chars();
+    let mut column = 0;
+
+    while let (Some(e), Some(a)) = (expected_chars.next(), actual_chars.next()) {
+        if e != a {
+            break;
+        }
+
+        column += 1;
+    }
+
+    column
+}
+
+/// Cuts a long line down to a window that contains the given column.
+fn cut_to_width(line: &str, column: usize) -> String {
+    let line = line.trim_end();
+    let num_chars = line.chars().count();
+
+    if num_chars <= MAX_LINE_WIDTH {
+        return line.to_string();
+    }
+
+    let start = column
+        .saturating_sub(MAX_LINE_WIDTH / 4)
+        .min(num_chars - MAX_LINE_WIDTH);
+    let end = start + MAX_LINE_WIDTH;
+
+    let window = line
+        .chars()
+        .skip(start)
+        .take(MAX_LINE_WIDTH)
+        .collect::<String>();
+    let prefix = if start > 0 { "…" } else { "" };
+    let suffix = if end < num_chars { "…" } else { "" };
+
+    format!("{prefix}{window}{suffix}")
+}
+
+/// Highlights the tokens of `line` that differ from the token at the same
+/// position in `other`.
+fn highlight(line: &str, other: &str, is_actual: bool) -> String {
+    let other_tokens = other.split(' ').collect::<Vec<_>>();
+
+    line.split(' ')
+        .enumerate()
+        .map(|(i, token)| {
+            if other_tokens.get(i) == Some(&token) {
+                token.to_string()
+            } else if is_actual {
+                token.bright_green().bold().to_string()
+            } else {
+                token.bright_red().bold().to_string()
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
@@ -3,12 +3,21 @@ use kattis_client::KattisClient;
 
 pub mod cli;
 mod commands;
+mod compile_cache;
 mod config;
+mod contest;
+mod diff;
 mod kattis_client;
+mod main_class;
 mod problem;
+mod reporter;
 mod solution;
+mod statement;
+mod submission;
+mod template;
 mod test_io;
 mod utils;
+mod validator;
 
 pub type Result<T> = eyre::Result<T>;
 
@@ -39,13 +48,18 @@ async fn try_run(args: cli::KittyArgs) -> crate::Result<()> {
     };
 
     match &app.args.subcommand {
+        Clean(args) => commands::clean(&app, args).await,
         Config(args) => commands::config(&app, args).await,
         Debug(args) => commands::debug(&app, args).await,
+        FetchSubmission(args) => commands::fetch_submission(&app, args).await,
         Get(args) => commands::get(&app, args).await,
         Langs => commands::langs(&app).await,
         Open(args) => commands::open(&app, args).await,
+        Show(args) => commands::show(&app, args).await,
+        Templates => commands::templates(&app).await,
         Test(args) => commands::test(&app, args).await,
         Submit(args) => commands::submit(&app, args).await,
+        Submissions(args) => commands::submissions(&app, args).await,
         Update => commands::update().await,
     }
 }
@@ -0,0 +1,225 @@
+use regex::Regex;
+
+/// Whether Kattis needs to be told the main class of submissions in the
+/// language with the given Kattis name.
+pub fn needs_main_class(kattis_language: &str) -> bool {
+    matches!(kattis_language, "Java" | "Kotlin" | "Scala")
+}
+
+/// Finds the class containing the entry point of a Java, Kotlin or Scala
+/// program:
+///
+///  - Java: the class with a `main` method.
+///  - Scala: the object with a `main` method or extending `App`, or the name
+///    of a `@main` method.
+///  - Kotlin: the object or class with a `main` function, or the class Kotlin
+///    generates for the file, such as `FooKt` for Foo.kt, for a top-level
+///    `main` function.
+///
+/// The class name includes the package, if any. Returns `None` if the language
+/// does not need a main class or none could be found.
+pub fn detect_main_class(kattis_language: &str, source: &str, file_stem: &str) -> Option<String> {
+    if !needs_main_class(kattis_language) {
+        return None;
+    }
+
+    let code = strip_comments_and_strings(source);
+    let main_class = find_main_class(kattis_language, source, &code, file_stem)?;
+
+    let package = Regex::new(r"(?m)^\s*package\s+([\w.]+)")
+        .unwrap()
+        .captures(&code)
+        .map(|captures| captures[1].to_string());
+
+    match package {
+        Some(package) => Some(format!("{package}.{main_class}")),
+        None => Some(main_class),
+    }
+}
+
+/// A block of code between braces. Blocks belonging to a class or object have
+/// its name.
+struct Block {
+    type_name: Option<String>,
+}
+
+fn find_main_class(
+    kattis_language: &str,
+    source: &str,
+    code: &str,
+    file_stem: &str,
+) -> Option<String> {
+    let token = Regex::new(
+        r"(?x)
+        @main \s+ def \s+ (?P<scala_main>\w+)
+        | \b (?P<kind>class|object|interface|enum|record) \s+ (?P<name>[A-Za-z_$][\w$]*)
+        | \b extends \s+ (?P<app>App) \b
+        | \b (?:void|def|fun) \s+ (?P<main>main) \s* \(
+        | (?P<open>\{)
+        | (?P<close>\})
+        ",
+    )
+    .unwrap();
+
+    let mut blocks: Vec<Block> = Vec::new();
+    // A class or object whose body has not been opened yet.
+    let mut pending_type: Option<(String, String)> = None;
+
+    for captures in token.captures_iter(code) {
+        if let Some(name) = captures.name("scala_main") {
+            return Some(name.as_str().to_string());
+        }
+
+        if let (Some(kind), Some(name)) = (captures.name("kind"), captures.name("name")) {
+            pending_type = Some((kind.as_str().to_string(), name.as_str().to_string()));
+        } else if captures.name("app").is_some() {
+            if let Some((kind, name)) = &pending_type {
+                if kind == "object" {
+                    return Some(nested_class_name(&blocks, name));
+                }
+            }
+        } else if captures.name("open").is_some() {
+            blocks.push(Block {
+                type_name: pending_type.take().map(|(_, name)| name),
+            });
+        } else if captures.name("close").is_some() {
+            blocks.pop();
+        } else if captures.name("main").is_some() {
+            let is_in_type = blocks.iter().any(|block| block.type_name.is_some());
+
+            if is_in_type {
+                return Some(nested_class_name(&blocks, ""));
+            }
+
+            // Kotlin places top-level functions in a class named after the
+            // file unless told otherwise.
+            if kattis_language == "Kotlin" && blocks.is_empty() {
+                return Some(
+                    kotlin_jvm_name(source).unwrap_or_else(|| kotlin_file_class_name(file_stem)),
+                );
+            }
+
+            pending_type = None;
+        }
+    }
+
+    None
+}
+
+/// The binary name of a class nested in the named blocks, such as
+/// `Outer$Inner`. The innermost name is appended if it is not empty.
+fn nested_class_name(blocks: &[Block], innermost: &str) -> String {
+    blocks
+        .iter()
+        .filter_map(|block| block.type_name.as_deref())
+        .chain(Some(innermost).filter(|name| !name.is_empty()))
+        .collect::<Vec<_>>()
+        .join("$")
+}
+
+/// The class name set with `@file:JvmName("...")` in a Kotlin file, if any.
+fn kotlin_jvm_name(source: &str) -> Option<String> {
+    Regex::new(r#"@file\s*:\s*JvmName\s*\(\s*"([\w$]+)"\s*\)"#)
+        .unwrap()
+        .captures(source)
+        .map(|captures| captures[1].to_string())
+}
+
+/// The name of the class Kotlin puts top-level declarations of a file in, such
+/// as `FooBarKt` for foo-bar.kt.
+fn kotlin_file_class_name(file_stem: &str) -> String {
+    let mut name = String::new();
+    let mut capitalise_next = true;
+
+    for c in file_stem.chars() {
+        if c.is_alphanumeric() || c == '_' {
+            if capitalise_next {
+                name.extend(c.to_uppercase());
+            } else {
+                name.push(c);
+            }
+
+            capitalise_next = false;
+        } else {
+            capitalise_next = true;
+        }
+    }
+
+    format!("{name}Kt")
+}
+
+/// Removes comments and the contents of string and character literals, such
+/// that they cannot be mistaken for code. Line breaks are kept.
+fn strip_comments_and_strings(source: &str) -> String {
+    let chars = source.chars().collect::<Vec<_>>();
+    let mut code = String::with_capacity(source.len());
+    let mut i = 0;
+
+    let starts_with = |i: usize, pattern: &str| {
+        pattern
+            .chars()
+            .enumerate()
+            .all(|(j, c)| chars.get(i + j) == Some(&c))
+    };
+
+    while i < chars.len() {
+        if starts_with(i, "//") {
+            while i < chars.len() && chars[i] != '\n' {
+                i += 1;
+            }
+        } else if starts_with(i, "/*") {
+            i += 2;
+
+            while i < chars.len() && !starts_with(i, "*/") {
+                if chars[i] == '\n' {
+                    code.push('\n');
+                }
+
+                i += 1;
+            }
+
+            i += 2;
+        } else if starts_with(i, "\"\"\"") {
+            // Text blocks and raw strings have no escapes.
+            i += 3;
+
+            while i < chars.len() && !starts_with(i, "\"\"\"") {
+                i += 1;
+            }
+
+            i += 3;
+            code.push_str("\"\"");
+        } else if chars[i] == '"' || is_char_literal(&chars, i) {
+            let quote = chars[i];
+            i += 1;
+
+            while i < chars.len() && chars[i] != quote && chars[i] != '\n' {
+                if chars[i] == '\\' {
+                    i += 1;
+                }
+
+                i += 1;
+            }
+
+            i += 1;
+            code.push(quote);
+            code.push(quote);
+        } else {
+            code.push(chars[i]);
+            i += 1;
+        }
+    }
+
+    code
+}
+
+/// Whether a character literal such as 'a' or '\n' starts at the index. Scala
+/// also uses single quotes for symbols, such as 'name, which are not literals.
+fn is_char_literal(chars: &[char], i: usize) -> bool {
+    chars[i] == '\''
+        && match chars.get(i + 1) {
+            Some('\\') => true,
+            Some(_) => chars.get(i + 2) == Some(&'\''),
+            None => false,
+        }
+}
@@ -1,19 +1,330 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use eyre::Context;
 use regex::Regex;
+use reqwest::{Client, StatusCode};
+use scraper::{Html, Selector};
+use yaml_rust::{yaml::Hash, Yaml, YamlEmitter, YamlLoader};
+
+use crate::{utils::resolve_and_get_file_name, App};
 
-use crate::App;
+/// The name of the file in a solution folder that describes the problem.
+pub const PROBLEM_FILE_NAME: &str = "problem.yml";
+/// The name of the file describing the problem in Kattis' problem packages.
+/// Its fields are used where the problem file does not have them.
+pub const PROBLEM_PACKAGE_FILE_NAME: &str = "problem.yaml";
 
 pub fn make_problem_url(app: &App, problem_id: &str) -> crate::Result<String> {
     let host_name = &app.config.try_kattisrc()?.kattis.host_name;
-    let url = format!("https://{host_name}/problems/{problem_id}");
-    Ok(url)
+    Ok(make_problem_url_on_host(
+        &format!("https://{host_name}"),
+        problem_id,
+    ))
+}
+
+/// Makes the URL of a problem on the Kattis instance at `host_url`, such as
+/// `https://open.kattis.com`.
+pub fn make_problem_url_on_host(host_url: &str, problem_id: &str) -> String {
+    format!("{}/problems/{problem_id}", host_url.trim_end_matches('/'))
+}
+
+/// Downloads the problem's page on Kattis. Returns `None` if the problem does
+/// not exist.
+pub async fn fetch_problem_page(app: &App, problem_id: &str) -> crate::Result<Option<String>> {
+    let url = make_problem_url(app, problem_id)?;
+    fetch_problem_page_at(&app.client, &url).await
+}
+
+/// Downloads the problem page at the given URL. Returns `None` if the problem
+/// does not exist.
+pub async fn fetch_problem_page_at(client: &Client, url: &str) -> crate::Result<Option<String>> {
+    let response = client
+        .get(url)
+        .send()
+        .await
+        .wrap_err("Failed to send request to Kattis")?;
+
+    match response.status() {
+        status if status.is_success() => {
+            let page = response
+                .text()
+                .await
+                .wrap_err("Failed to read problem page from Kattis")?;
+
+            Ok(Some(page))
+        }
+        StatusCode::NOT_FOUND => Ok(None),
+        status => eyre::bail!("Failed to get problem from Kattis (http status code: {status})"),
+    }
 }
 
 pub fn make_problem_sample_tests_zip_url(app: &App, problem_id: &str) -> crate::Result<String> {
     let problem_url = make_problem_url(app, problem_id)?;
-    let zip_url = format!("{problem_url}/file/statement/samples.zip");
-    Ok(zip_url)
+    Ok(make_sample_tests_zip_url(&problem_url))
+}
+
+pub fn make_sample_tests_zip_url(problem_url: &str) -> String {
+    format!("{problem_url}/file/statement/samples.zip")
+}
+
+/// Finds the ID of the problem solved in the solution folder. It is read from
+/// the problem file if it has an `id` field, such as for folders named after a
+/// problem's letter in a contest, and otherwise it is the folder's name.
+pub fn problem_id_from_folder(solution_dir: impl AsRef<Path>) -> crate::Result<String> {
+    let solution_dir = solution_dir.as_ref();
+    let id = load_problem_file(solution_dir)?.and_then(|doc| yaml_to_string(&doc["id"]));
+
+    match id {
+        Some(id) => Ok(id),
+        None => resolve_and_get_file_name(solution_dir),
+    }
 }
 
 pub fn problem_id_is_legal(problem_id: &str) -> bool {
     Regex::new(r"^[\w\d\.]+$").unwrap().is_match(problem_id)
 }
+
+/// The details Kattis shows about a problem, as stored in the problem file.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct ProblemMetadata {
+    pub name: Option<String>,
+    pub time_limit: Option<Duration>,
+    /// In megabytes.
+    pub memory_limit: Option<u64>,
+    /// Either a single number or a range, such as "2.3 - 4.1", possibly
+    /// followed by a description such as "Medium".
+    pub difficulty: Option<String>,
+}
+
+impl ProblemMetadata {
+    /// Reads the metadata from the problem file in the solution folder. Missing
+    /// fields are left empty.
+    pub fn load(solution_dir: impl AsRef<Path>) -> crate::Result<Self> {
+        let Some(doc) = load_problem_file(solution_dir)? else {
+            return Ok(Self::default());
+        };
+
+        let time_limit = match &doc["time_limit"] {
+            Yaml::BadValue | Yaml::Null => None,
+            value => {
+                let secs = match value {
+                    Yaml::Integer(secs) => Some(*secs as f64),
+                    value => value.as_f64(),
+                }
+                .ok_or_else(|| {
+                    eyre::eyre!("The 'time_limit' field of the problem file must be a number")
+                })?;
+
+                eyre::ensure!(
+                    secs > 0.0,
+                    "The 'time_limit' field of the problem file must be a positive number"
+                );
+
+                Some(
+                    Duration::try_from_secs_f64(secs)
+                        .wrap_err("The 'time_limit' field of the problem file is invalid")?,
+                )
+            }
+        };
+
+        let memory_limit = match &doc["memory_limit"] {
+            Yaml::Integer(mb) if *mb > 0 => Some(*mb as u64),
+            Yaml::Integer(_) => eyre::bail!(
+                "The 'memory_limit' field of the problem file must be a positive number"
+            ),
+            Yaml::BadValue | Yaml::Null => None,
+            _ => eyre::bail!("The 'memory_limit' field of the problem file must be a number"),
+        };
+
+        Ok(Self {
+            name: yaml_to_string(&doc["name"]),
+            time_limit,
+            memory_limit,
+            difficulty: yaml_to_string(&doc["difficulty"]),
+        })
+    }
+
+    /// Scrapes the metadata from a problem's page on Kattis. Details that are
+    /// not found are left empty.
+    pub fn from_problem_page(page_html: &str) -> Self {
+        let page = Html::parse_document(page_html);
+
+        let name = page
+            .select(&Selector::parse("h1.book-page-heading").unwrap())
+            .chain(page.select(&Selector::parse("h1").unwrap()))
+            .next()
+            .map(|title| collapse_whitespace(&title.text().collect::<String>()));
+
+        // The details are matched in the text of the page since the layout of
+        // the sidebar they are shown in changes from time to time.
+        let text = collapse_whitespace(&page.root_element().text().collect::<Vec<_>>().join(" "));
+        let find = |pattern: &str| {
+            Regex::new(pattern)
+                .unwrap()
+                .captures(&text)
+                .map(|captures| captures[1].to_string())
+        };
+
+        Self {
+            name: name.filter(|name| !name.is_empty()),
+            time_limit: find(r"(?i)CPU Time limit:?\s*(\d+(?:\.\d+)?)\s*seconds?")
+                .and_then(|secs| secs.parse().ok())
+                .and_then(|secs| Duration::try_from_secs_f64(secs).ok()),
+            memory_limit: find(r"(?i)Memory limit:?\s*(\d+)\s*MB").and_then(|mb| mb.parse().ok()),
+            difficulty: find(
+                r"(?i)Difficulty:?\s*(\d+(?:\.\d+)?(?:\s*-\s*\d+(?:\.\d+)?)?(?:\s*(?:Easy|Medium|Hard))?)",
+            ),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Writes the metadata to the problem file in the solution folder, keeping
+    /// any other fields in it.
+    pub fn save(&self, solution_dir: impl AsRef<Path>) -> crate::Result<()> {
+        let solution_dir = solution_dir.as_ref();
+
+        let fields = [
+            ("name", self.name.clone().map(Yaml::String)),
+            (
+                "time_limit",
+                self.time_limit
+                    .map(|limit| Yaml::Real(limit.as_secs_f64().to_string())),
+            ),
+            (
+                "memory_limit",
+                self.memory_limit
+                    .and_then(|mb| i64::try_from(mb).ok())
+                    .map(Yaml::Integer),
+            ),
+            ("difficulty", self.difficulty.clone().map(Yaml::String)),
+        ];
+
+        update_problem_file(solution_dir, fields)
+    }
+}
+
+/// Saves the problem's ID in the problem file. This is needed when the
+/// solution folder is not named after the problem.
+pub fn save_problem_id(solution_dir: impl AsRef<Path>, problem_id: &str) -> crate::Result<()> {
+    update_problem_file(
+        solution_dir.as_ref(),
+        [("id", Some(Yaml::String(problem_id.to_string())))],
+    )
+}
+
+/// Sets the given fields in the problem file in the solution folder, keeping
+/// any other fields in it. Fields without a value are left as they are.
+///
+/// Only kitty's own problem file is written. A problem package's problem.yaml
+/// is left untouched, and its fields are still read where the problem file
+/// does not have them.
+fn update_problem_file<'a>(
+    solution_dir: &Path,
+    fields: impl IntoIterator<Item = (&'a str, Option<Yaml>)>,
+) -> crate::Result<()> {
+    let path = solution_dir.join(PROBLEM_FILE_NAME);
+
+    let mut doc = match load_yaml_file(&path)? {
+        Some(Yaml::Hash(doc)) => doc,
+        _ => Hash::new(),
+    };
+
+    for (key, value) in fields {
+        if let Some(value) = value {
+            doc.insert(Yaml::String(key.to_string()), value);
+        }
+    }
+
+    let mut contents = String::new();
+    YamlEmitter::new(&mut contents)
+        .dump(&Yaml::Hash(doc))
+        .wrap_err("Failed to create problem file")?;
+
+    fs::write(&path, format!("{}\n", contents.trim_start_matches("---\n")))
+        .wrap_err_with(|| format!("Failed to write problem file at '{}'", path.display()))
+}
+
+fn yaml_to_string(value: &Yaml) -> Option<String> {
+    match value {
+        Yaml::String(s) => Some(s.clone()),
+        Yaml::Real(s) => Some(s.clone()),
+        Yaml::Integer(i) => Some(i.to_string()),
+        _ => None,
+    }
+}
+
+fn collapse_whitespace(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Reads the `validator_flags` field of the problem file in the solution
+/// folder, if there is one. The flags use the same format as Kattis' problem
+/// packages.
+pub fn read_validator_flags(solution_dir: impl AsRef<Path>) -> crate::Result<Option<String>> {
+    let flags = load_problem_file(solution_dir)?
+        .and_then(|doc| doc["validator_flags"].as_str().map(str::to_string));
+
+    Ok(flags)
+}
+
+/// Reads the `validator` field of the problem file in the solution folder, if
+/// there is one. The path is relative to the solution folder.
+pub fn read_validator_path(solution_dir: impl AsRef<Path>) -> crate::Result<Option<PathBuf>> {
+    read_path_field(solution_dir, "validator")
+}
+
+/// Reads the `interactor` field of the problem file in the solution folder, if
+/// there is one. The path is relative to the solution folder.
+pub fn read_interactor_path(solution_dir: impl AsRef<Path>) -> crate::Result<Option<PathBuf>> {
+    read_path_field(solution_dir, "interactor")
+}
+
+fn read_path_field(solution_dir: impl AsRef<Path>, key: &str) -> crate::Result<Option<PathBuf>> {
+    let solution_dir = solution_dir.as_ref();
+    let path = load_problem_file(solution_dir)?
+        .and_then(|doc| doc[key].as_str().map(|path| solution_dir.join(path)));
+
+    Ok(path)
+}
+
+/// Reads the problem file in the solution folder. Fields it does not have are
+/// read from the problem.yaml file of a problem package, if there is one.
+fn load_problem_file(solution_dir: impl AsRef<Path>) -> crate::Result<Option<Yaml>> {
+    let solution_dir = solution_dir.as_ref();
+
+    let problem_file = load_yaml_file(&solution_dir.join(PROBLEM_FILE_NAME))?;
+    let package_file = load_yaml_file(&solution_dir.join(PROBLEM_PACKAGE_FILE_NAME))?;
+
+    let doc = match (problem_file, package_file) {
+        (Some(Yaml::Hash(mut doc)), Some(Yaml::Hash(package_doc))) => {
+            for (key, value) in package_doc {
+                doc.entry(key).or_insert(value);
+            }
+
+            Some(Yaml::Hash(doc))
+        }
+        (problem_file, package_file) => problem_file.or(package_file),
+    };
+
+    Ok(doc)
+}
+
+fn load_yaml_file(path: &Path) -> crate::Result<Option<Yaml>> {
+    if !path.exists() {
+        return Ok(None);
+    }
+
+    let contents = fs::read_to_string(path)
+        .wrap_err_with(|| format!("Failed to read problem file at '{}'", path.display()))?;
+    let docs = YamlLoader::load_from_str(&contents)
+        .wrap_err_with(|| format!("Failed to parse problem file at '{}'", path.display()))?;
+
+    Ok(docs.into_iter().next())
+}
@@ -0,0 +1,95 @@
+use std::io::{stdout, Write};
+
+use colored::Colorize;
+use eyre::Context;
+
+use crate::{
+    test_io::{format_memory, FileTestCase, TestCaseResult},
+    App,
+};
+
+use super::{Reporter, TestSummary, FAILURE, SUCCESS};
+
+/// Prints results for humans to read in a terminal.
+pub struct HumanReporter<'a> {
+    app: &'a App,
+    show_time: bool,
+}
+
+impl<'a> HumanReporter<'a> {
+    pub fn new(app: &'a App, show_time: bool) -> Self {
+        Self { app, show_time }
+    }
+}
+
+impl Reporter for HumanReporter<'_> {
+    fn start(&mut self, num_tests: usize) -> crate::Result<()> {
+        println!("Running {num_tests} tests\n");
+        Ok(())
+    }
+
+    fn test_started(&mut self, test_case: &FileTestCase) -> crate::Result<()> {
+        print!("test {} ... ", test_case.name);
+        stdout().flush().wrap_err("Failed to flush output")
+    }
+
+    fn test_finished(
+        &mut self,
+        _test_case: &FileTestCase,
+        outcome: &TestCaseResult,
+    ) -> crate::Result<()> {
+        print!("{}", if outcome.is_ok() { SUCCESS } else { FAILURE });
+
+        if self.show_time {
+            if let Ok(test_info) = outcome {
+                print!(" in {:.2}s", test_info.running_time.as_secs_f64());
+
+                if let Some(peak_memory) = test_info.peak_memory {
+                    print!(", {}", format_memory(peak_memory));
+                }
+            }
+        }
+
+        println!();
+
+        if let Err(test_case_error) = outcome {
+            test_case_error.print(self.app)?;
+        }
+
+        Ok(())
+    }
+
+    fn finish(&mut self, summary: &TestSummary) -> crate::Result<()> {
+        if !summary.groups.is_empty() {
+            println!();
+        }
+
+        for (name, group) in &summary.groups {
+            let outcome = if group.is_success() {
+                "ok".bright_green()
+            } else {
+                "failed".bright_red()
+            };
+
+            println!(
+                "Group {}: {outcome}. {} passed; {} failed.",
+                name.bright_cyan(),
+                group.num_passed,
+                group.num_failed
+            );
+        }
+
+        let overall_outcome = if summary.is_success() {
+            "ok".bright_green()
+        } else {
+            "failed".bright_red()
+        };
+
+        println!(
+            "\nTest result: {overall_outcome}. {} passed; {} failed.",
+            summary.num_passed, summary.num_failed
+        );
+
+        Ok(())
+    }
+}
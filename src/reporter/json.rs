@@ -0,0 +1,236 @@
+use std::{
+    collections::BTreeMap,
+    io::{stdout, Write},
+};
+
+use eyre::Context;
+use serde::Serialize;
+
+use crate::test_io::{FileTestCase, TestCaseError, TestCaseResult};
+
+use super::{verdict, Reporter, TestSummary};
+
+/// Longer outputs are cut off to keep records at a reasonable size.
+const MAX_EXCERPT_LEN: usize = 4096;
+const TRUNCATION_MARKER: &str = "\n... (truncated)";
+
+/// Prints results as JSON. In streaming mode, every record is printed on its
+/// own line as soon as it is ready (JSON Lines). Otherwise, a single JSON
+/// document is printed once all tests have run.
+pub struct JsonReporter {
+    streaming: bool,
+    tests: Vec<TestRecord>,
+}
+
+#[derive(Debug, Serialize)]
+struct TestRecord {
+    name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    group: Option<String>,
+    verdict: &'static str,
+    /// In seconds.
+    running_time: f64,
+    /// In bytes.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    peak_memory: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    expected: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    actual: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    stderr: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    feedback: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    transcript: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+struct SummaryRecord {
+    result: &'static str,
+    total: usize,
+    passed: usize,
+    failed: usize,
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    groups: BTreeMap<String, GroupRecord>,
+}
+
+#[derive(Debug, Serialize)]
+struct GroupRecord {
+    result: &'static str,
+    passed: usize,
+    failed: usize,
+}
+
+#[derive(Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum StreamRecord<'a> {
+    Test(&'a TestRecord),
+    Summary(&'a SummaryRecord),
+}
+
+#[derive(Serialize)]
+struct Document<'a> {
+    tests: &'a [TestRecord],
+    summary: &'a SummaryRecord,
+}
+
+impl JsonReporter {
+    pub fn new(streaming: bool) -> Self {
+        Self {
+            streaming,
+            tests: Vec::new(),
+        }
+    }
+}
+
+impl Reporter for JsonReporter {
+    fn start(&mut self, _num_tests: usize) -> crate::Result<()> {
+        self.tests.clear();
+        Ok(())
+    }
+
+    fn test_started(&mut self, _test_case: &FileTestCase) -> crate::Result<()> {
+        Ok(())
+    }
+
+    fn test_finished(
+        &mut self,
+        test_case: &FileTestCase,
+        outcome: &TestCaseResult,
+    ) -> crate::Result<()> {
+        let record = TestRecord::new(test_case, outcome);
+
+        if self.streaming {
+            print_json_line(&StreamRecord::Test(&record))?;
+        }
+
+        self.tests.push(record);
+
+        Ok(())
+    }
+
+    fn finish(&mut self, summary: &TestSummary) -> crate::Result<()> {
+        let summary = SummaryRecord {
+            result: if summary.is_success() { "ok" } else { "failed" },
+            total: summary.num_passed + summary.num_failed,
+            passed: summary.num_passed,
+            failed: summary.num_failed,
+            groups: summary
+                .groups
+                .iter()
+                .map(|(name, group)| {
+                    let record = GroupRecord {
+                        result: if group.is_success() { "ok" } else { "failed" },
+                        passed: group.num_passed,
+                        failed: group.num_failed,
+                    };
+
+                    (name.clone(), record)
+                })
+                .collect(),
+        };
+
+        if self.streaming {
+            print_json_line(&StreamRecord::Summary(&summary))
+        } else {
+            print_json_line(&Document {
+                tests: &self.tests,
+                summary: &summary,
+            })
+        }
+    }
+}
+
+impl TestRecord {
+    fn new(test_case: &FileTestCase, outcome: &TestCaseResult) -> Self {
+        let mut record = Self {
+            name: test_case.name.clone(),
+            group: test_case.group.clone(),
+            verdict: verdict(outcome),
+            running_time: 0.0,
+            peak_memory: None,
+            expected: None,
+            actual: None,
+            stderr: None,
+            feedback: None,
+            transcript: None,
+        };
+
+        let test_case_error = match outcome {
+            Ok(test_info) => {
+                record.running_time = test_info.running_time.as_secs_f64();
+                record.peak_memory = test_info.peak_memory;
+                return record;
+            }
+            Err(test_case_error) => test_case_error,
+        };
+
+        record.running_time = test_case_error.running_time().as_secs_f64();
+
+        match test_case_error {
+            TestCaseError::WrongAnswer {
+                expected,
+                actual,
+                stderr,
+                feedback,
+                ..
+            } => {
+                record.expected = Some(excerpt(expected));
+                record.actual = Some(excerpt(actual));
+                record.stderr = Some(excerpt(stderr));
+                record.feedback = feedback.as_deref().map(excerpt);
+            }
+            TestCaseError::RuntimeError { stdout, stderr, .. }
+            | TestCaseError::TimeLimitExceeded { stdout, stderr, .. } => {
+                record.actual = Some(excerpt(stdout));
+                record.stderr = Some(excerpt(stderr));
+            }
+            TestCaseError::MemoryLimitExceeded {
+                stdout,
+                stderr,
+                peak_memory,
+                ..
+            } => {
+                record.peak_memory = *peak_memory;
+                record.actual = Some(excerpt(stdout));
+                record.stderr = Some(excerpt(stderr));
+            }
+            TestCaseError::WrongInteraction {
+                transcript,
+                stderr,
+                feedback,
+                ..
+            } => {
+                record.transcript = Some(excerpt(transcript));
+                record.stderr = Some(excerpt(stderr));
+                record.feedback = feedback.as_deref().map(excerpt);
+            }
+        }
+
+        record
+    }
+}
+
+fn excerpt(text: &str) -> String {
+    let text = text.trim_end();
+
+    if text.len() <= MAX_EXCERPT_LEN {
+        return text.to_string();
+    }
+
+    let mut end = MAX_EXCERPT_LEN;
+    while !text.is_char_boundary(end) {
+        end -= 1;
+    }
+
+    format!("{}{TRUNCATION_MARKER}", &text[..end])
+}
+
+fn print_json_line(value: &impl Serialize) -> crate::Result<()> {
+    let mut stdout = stdout().lock();
+
+    serde_json::to_writer(&mut stdout, value).wrap_err("Failed to write JSON output")?;
+    writeln!(stdout).wrap_err("Failed to write JSON output")?;
+    stdout.flush().wrap_err("Failed to flush output")
+}
@@ -0,0 +1,129 @@
+use std::{
+    fmt::{self, Write},
+    path::Path,
+};
+
+use eyre::Context;
+
+use crate::test_io::{FileTestCase, TestCaseResult};
+
+use super::{write_report, FinishedTest, Reporter, TestSummary};
+
+/// Writes the results to a JUnit XML file once all tests have run. The test
+/// cases of the problem are grouped in a single test suite.
+pub struct JunitReporter<'a> {
+    path: &'a Path,
+    problem_id: &'a str,
+    tests: Vec<FinishedTest>,
+}
+
+impl<'a> JunitReporter<'a> {
+    pub fn new(path: &'a Path, problem_id: &'a str) -> Self {
+        Self {
+            path,
+            problem_id,
+            tests: Vec::new(),
+        }
+    }
+}
+
+impl Reporter for JunitReporter<'_> {
+    fn start(&mut self, _num_tests: usize) -> crate::Result<()> {
+        self.tests.clear();
+        Ok(())
+    }
+
+    fn test_started(&mut self, _test_case: &FileTestCase) -> crate::Result<()> {
+        Ok(())
+    }
+
+    fn test_finished(
+        &mut self,
+        test_case: &FileTestCase,
+        outcome: &TestCaseResult,
+    ) -> crate::Result<()> {
+        self.tests.push(FinishedTest::new(test_case, outcome));
+        Ok(())
+    }
+
+    fn finish(&mut self, summary: &TestSummary) -> crate::Result<()> {
+        let mut xml = String::new();
+        self.write_xml(&mut xml, summary)
+            .wrap_err("Failed to create JUnit report")?;
+
+        write_report(self.path, "JUnit", &xml)
+    }
+}
+
+impl JunitReporter<'_> {
+    fn write_xml(&self, xml: &mut impl Write, summary: &TestSummary) -> fmt::Result {
+        let total = summary.num_passed + summary.num_failed;
+        let total_time: f64 = self.tests.iter().map(|test| test.running_time).sum();
+        let problem_id = escape_xml(self.problem_id);
+
+        writeln!(xml, r#"<?xml version="1.0" encoding="UTF-8"?>"#)?;
+        writeln!(
+            xml,
+            r#"<testsuites name="kitty" tests="{total}" failures="{}" errors="0" time="{total_time:.3}">"#,
+            summary.num_failed
+        )?;
+        writeln!(
+            xml,
+            r#"  <testsuite name="{problem_id}" tests="{total}" failures="{}" errors="0" skipped="0" time="{total_time:.3}">"#,
+            summary.num_failed
+        )?;
+
+        for test in &self.tests {
+            // Groups are part of the class name such that tools can group the
+            // test cases the same way.
+            let classname = match &test.group {
+                Some(group) => format!("{problem_id}.{}", escape_xml(group)),
+                None => problem_id.clone(),
+            };
+
+            write!(
+                xml,
+                r#"    <testcase name="{}" classname="{classname}" time="{:.3}""#,
+                escape_xml(&test.name),
+                test.running_time
+            )?;
+
+            match &test.message {
+                None => writeln!(xml, "/>")?,
+                Some(message) => {
+                    writeln!(xml, ">")?;
+                    writeln!(
+                        xml,
+                        r#"      <failure type="{verdict}" message="{verdict}">{}</failure>"#,
+                        escape_xml(message),
+                        verdict = test.verdict,
+                    )?;
+                    writeln!(xml, "    </testcase>")?;
+                }
+            }
+        }
+
+        writeln!(xml, "  </testsuite>")?;
+        writeln!(xml, "</testsuites>")
+    }
+}
+
+fn escape_xml(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            // Other control characters are not allowed in XML documents.
+            '\t' | '\n' | '\r' => escaped.push(c),
+            c if c.is_control() => escaped.push(char::REPLACEMENT_CHARACTER),
+            c => escaped.push(c),
+        }
+    }
+
+    escaped
+}
@@ -0,0 +1,201 @@
+use std::{collections::BTreeMap, fs, path::Path};
+
+use eyre::Context;
+
+use crate::{
+    cli::{OutputFormat, TestArgs},
+    test_io::{FileTestCase, TestCaseError, TestCaseResult},
+    App,
+};
+
+use self::{human::HumanReporter, json::JsonReporter, junit::JunitReporter, tap::TapReporter};
+
+mod human;
+mod json;
+mod junit;
+mod tap;
+
+pub const SUCCESS: &str = "✅";
+pub const FAILURE: &str = "❌";
+
+/// Reports the progress and outcomes of a test run.
+pub trait Reporter {
+    fn start(&mut self, num_tests: usize) -> crate::Result<()>;
+
+    /// Called right before waiting for the outcome of a test case.
+    fn test_started(&mut self, test_case: &FileTestCase) -> crate::Result<()>;
+
+    fn test_finished(
+        &mut self,
+        test_case: &FileTestCase,
+        outcome: &TestCaseResult,
+    ) -> crate::Result<()>;
+
+    fn finish(&mut self, summary: &TestSummary) -> crate::Result<()>;
+}
+
+#[derive(Debug, Default)]
+pub struct TestSummary {
+    pub num_passed: usize,
+    pub num_failed: usize,
+    /// The results of each group of test cases, including the test cases in
+    /// their subgroups.
+    pub groups: BTreeMap<String, GroupSummary>,
+}
+
+#[derive(Debug, Default)]
+pub struct GroupSummary {
+    pub num_passed: usize,
+    pub num_failed: usize,
+}
+
+impl TestSummary {
+    pub fn add(&mut self, test_case: &FileTestCase, outcome: &TestCaseResult) {
+        let (num_passed, num_failed) = if outcome.is_ok() { (1, 0) } else { (0, 1) };
+
+        self.num_passed += num_passed;
+        self.num_failed += num_failed;
+
+        // Test cases count towards their group and all groups containing it.
+        let groups = test_case.group.iter().flat_map(|group| {
+            group
+                .match_indices('/')
+                .map(|(i, _)| &group[..i])
+                .chain([group.as_str()])
+        });
+
+        for group in groups {
+            let group = self.groups.entry(group.to_string()).or_default();
+            group.num_passed += num_passed;
+            group.num_failed += num_failed;
+        }
+    }
+
+    pub fn is_success(&self) -> bool {
+        self.num_failed == 0
+    }
+}
+
+impl GroupSummary {
+    pub fn is_success(&self) -> bool {
+        self.num_failed == 0
+    }
+}
+
+/// Creates the reporter printing results in the chosen format along with any
+/// reporters writing results to files.
+pub fn create_reporter<'a>(
+    app: &'a App,
+    args: &'a TestArgs,
+    problem_id: &'a str,
+) -> Box<dyn Reporter + 'a> {
+    let output_reporter: Box<dyn Reporter + 'a> = match args.format {
+        OutputFormat::Human => Box::new(HumanReporter::new(app, args.time)),
+        OutputFormat::Json => Box::new(JsonReporter::new(false)),
+        OutputFormat::Jsonl => Box::new(JsonReporter::new(true)),
+    };
+
+    let mut reporters = vec![output_reporter];
+
+    if let Some(path) = &args.junit {
+        reporters.push(Box::new(JunitReporter::new(path, problem_id)));
+    }
+
+    if let Some(path) = &args.tap {
+        reporters.push(Box::new(TapReporter::new(path)));
+    }
+
+    if reporters.len() == 1 {
+        return reporters.remove(0);
+    }
+
+    Box::new(CombinedReporter { reporters })
+}
+
+/// Passes everything on to several reporters.
+struct CombinedReporter<'a> {
+    reporters: Vec<Box<dyn Reporter + 'a>>,
+}
+
+impl Reporter for CombinedReporter<'_> {
+    fn start(&mut self, num_tests: usize) -> crate::Result<()> {
+        self.reporters
+            .iter_mut()
+            .try_for_each(|reporter| reporter.start(num_tests))
+    }
+
+    fn test_started(&mut self, test_case: &FileTestCase) -> crate::Result<()> {
+        self.reporters
+            .iter_mut()
+            .try_for_each(|reporter| reporter.test_started(test_case))
+    }
+
+    fn test_finished(
+        &mut self,
+        test_case: &FileTestCase,
+        outcome: &TestCaseResult,
+    ) -> crate::Result<()> {
+        self.reporters
+            .iter_mut()
+            .try_for_each(|reporter| reporter.test_finished(test_case, outcome))
+    }
+
+    fn finish(&mut self, summary: &TestSummary) -> crate::Result<()> {
+        self.reporters
+            .iter_mut()
+            .try_for_each(|reporter| reporter.finish(summary))
+    }
+}
+
+/// A finished test case as recorded by the reporters writing to files.
+struct FinishedTest {
+    name: String,
+    group: Option<String>,
+    verdict: &'static str,
+    running_time: f64,
+    /// The explanation of the failure without colours, if the test failed.
+    message: Option<String>,
+}
+
+impl FinishedTest {
+    fn new(test_case: &FileTestCase, outcome: &TestCaseResult) -> Self {
+        let (running_time, message) = match outcome {
+            Ok(test_info) => (test_info.running_time, None),
+            Err(test_case_error) => (
+                test_case_error.running_time(),
+                Some(strip_colours(&test_case_error.details(true).to_string())),
+            ),
+        };
+
+        Self {
+            name: test_case.name.clone(),
+            group: test_case.group.clone(),
+            verdict: verdict(outcome),
+            running_time: running_time.as_secs_f64(),
+            message,
+        }
+    }
+}
+
+/// A short, stable name for the outcome of a test case.
+fn verdict(outcome: &TestCaseResult) -> &'static str {
+    match outcome {
+        Ok(_) => "accepted",
+        Err(TestCaseError::WrongAnswer { .. }) => "wrong_answer",
+        Err(TestCaseError::RuntimeError { .. }) => "runtime_error",
+        Err(TestCaseError::TimeLimitExceeded { .. }) => "time_limit_exceeded",
+        Err(TestCaseError::MemoryLimitExceeded { .. }) => "memory_limit_exceeded",
+        Err(TestCaseError::WrongInteraction { .. }) => "wrong_interaction",
+    }
+}
+
+fn strip_colours(text: &str) -> String {
+    strip_ansi_escapes::strip(text)
+        .map(|bytes| String::from_utf8_lossy(&bytes).trim_end().to_string())
+        .unwrap_or_else(|_| text.trim_end().to_string())
+}
+
+fn write_report(path: &Path, kind: &str, contents: &str) -> crate::Result<()> {
+    fs::write(path, contents)
+        .wrap_err_with(|| format!("Failed to write {kind} report to '{}'", path.display()))
+}
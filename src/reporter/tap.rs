@@ -0,0 +1,92 @@
+use std::{
+    fmt::{self, Write},
+    path::Path,
+};
+
+use eyre::Context;
+
+use crate::test_io::{FileTestCase, TestCaseResult};
+
+use super::{write_report, FinishedTest, Reporter, TestSummary};
+
+/// Writes the results to a file in the Test Anything Protocol (version 13)
+/// once all tests have run. Failed tests include a YAML block with details.
+pub struct TapReporter<'a> {
+    path: &'a Path,
+    tests: Vec<FinishedTest>,
+}
+
+impl<'a> TapReporter<'a> {
+    pub fn new(path: &'a Path) -> Self {
+        Self {
+            path,
+            tests: Vec::new(),
+        }
+    }
+}
+
+impl Reporter for TapReporter<'_> {
+    fn start(&mut self, _num_tests: usize) -> crate::Result<()> {
+        self.tests.clear();
+        Ok(())
+    }
+
+    fn test_started(&mut self, _test_case: &FileTestCase) -> crate::Result<()> {
+        Ok(())
+    }
+
+    fn test_finished(
+        &mut self,
+        test_case: &FileTestCase,
+        outcome: &TestCaseResult,
+    ) -> crate::Result<()> {
+        self.tests.push(FinishedTest::new(test_case, outcome));
+        Ok(())
+    }
+
+    fn finish(&mut self, _summary: &TestSummary) -> crate::Result<()> {
+        let mut tap = String::new();
+        self.write_tap(&mut tap)
+            .wrap_err("Failed to create TAP report")?;
+
+        write_report(self.path, "TAP", &tap)
+    }
+}
+
+impl TapReporter<'_> {
+    fn write_tap(&self, tap: &mut impl Write) -> fmt::Result {
+        writeln!(tap, "TAP version 13")?;
+        writeln!(tap, "1..{}", self.tests.len())?;
+
+        for (i, test) in self.tests.iter().enumerate() {
+            let status = if test.message.is_none() {
+                "ok"
+            } else {
+                "not ok"
+            };
+            // A '#' starts a directive in TAP, so it must be escaped in names.
+            let name = test.name.replace('#', "\\#");
+
+            writeln!(tap, "{status} {} - {name}", i + 1)?;
+            writeln!(tap, "  ---")?;
+            writeln!(tap, "  verdict: {}", test.verdict)?;
+            writeln!(tap, "  running_time: {:.3}", test.running_time)?;
+
+            if let Some(message) = &test.message {
+                writeln!(tap, "  message: |")?;
+
+                for line in message.lines() {
+                    if line.is_empty() {
+                        writeln!(tap)?;
+                    } else {
+                        writeln!(tap, "    {line}")?;
+                    }
+                }
+            }
+
+            writeln!(tap, "  ...")?;
+        }
+
+        Ok(())
+    }
+}
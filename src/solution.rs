@@ -3,6 +3,7 @@ use std::{
     ffi::OsStr,
     fs,
     path::{Path, PathBuf},
+    time::Duration,
 };
 
 use color_eyre::owo_colors::OwoColorize;
@@ -10,7 +11,8 @@ use eyre::{Context, ContextCompat};
 
 use crate::{
     config::language::Language,
-    test_io::FileTestCase,
+    problem::problem_id_from_folder,
+    test_io::{FileTestCase, ResourceLimits, BYTES_PER_MEGABYTE},
     utils::{get_full_path, resolve_and_get_file_name},
     App,
 };
@@ -37,7 +39,7 @@ impl<'a> Solution<'a> {
             solution_dir.display().underline()
         );
 
-        let problem_id = resolve_and_get_file_name(&solution_dir)
+        let problem_id = problem_id_from_folder(&solution_dir)
             .wrap_err("Failed to extract problem ID from the solution folder")?;
 
         let solution_file = resolve_solution_file_to_use(app, &solution_dir, &options)?;
@@ -65,6 +67,21 @@ impl<'a> Solution<'a> {
     pub fn debug_save_dir(&self) -> PathBuf {
         self.debug_dir().join("saved")
     }
+
+    /// Resolves the resource limits to run the solution with. The time limit is
+    /// scaled by the language's time limit multiplier.
+    pub fn resource_limits(
+        &self,
+        time_limit: Option<Duration>,
+        memory_limit_mb: Option<u64>,
+    ) -> ResourceLimits {
+        let multiplier = self.lang.time_limit_multiplier();
+
+        ResourceLimits {
+            time: time_limit.map(|limit| limit.mul_f64(multiplier)),
+            memory: memory_limit_mb.map(|mb| mb.saturating_mul(BYTES_PER_MEGABYTE)),
+        }
+    }
 }
 
 #[derive(Debug)]
@@ -73,6 +90,9 @@ pub struct SolutionOptions<'a> {
     pub lang: Option<&'a String>,
 }
 
+/// The name of the folder with test cases in Kattis' problem package format.
+const PROBLEM_PACKAGE_DATA_DIR: &str = "data";
+
 pub fn get_test_dir(solution_dir: impl AsRef<Path>) -> PathBuf {
     solution_dir.as_ref().join("test")
 }
@@ -147,19 +167,29 @@ pub fn get_all_files_with_known_extension(
     Ok(options)
 }
 
+/// Finds the folder containing the test cases of a solution. This is the 'test'
+/// folder, or the 'data' folder of a Kattis problem package if there is no
+/// 'test' folder.
+pub fn find_test_dir(solution_dir: impl AsRef<Path>) -> PathBuf {
+    let solution_dir = solution_dir.as_ref();
+    let test_dir = get_test_dir(solution_dir);
+    let package_data_dir = solution_dir.join(PROBLEM_PACKAGE_DATA_DIR);
+
+    if !test_dir.exists() && package_data_dir.is_dir() {
+        package_data_dir
+    } else {
+        test_dir
+    }
+}
+
+/// Finds the test cases in the test folder and its subfolders. Test cases are
+/// named by their path relative to the test folder, such as 'sample/1', and
+/// the subfolder they are in is their group.
 pub fn get_test_cases(solution_dir: impl AsRef<Path>) -> crate::Result<Vec<FileTestCase>> {
-    let test_dir_files = get_test_dir(solution_dir)
-        .read_dir()
-        .wrap_err("Failed to read test case folder")?
-        .filter_map(Result::ok)
-        .filter(|entry| {
-            entry
-                .file_type()
-                .ok()
-                .map(|file_type| file_type.is_file())
-                .unwrap_or(false)
-        })
-        .map(|entry| entry.path());
+    let test_dir = find_test_dir(solution_dir);
+
+    let mut test_dir_files = Vec::new();
+    collect_files(&test_dir, &mut test_dir_files).wrap_err("Failed to read test case folder")?;
 
     // We use a BTreeMap to easily sort the test cases by name
     let mut input_files = BTreeMap::new();
@@ -177,8 +207,21 @@ pub fn get_test_cases(solution_dir: impl AsRef<Path>) -> crate::Result<Vec<FileT
             _ => continue,
         };
 
-        if let Some(name) = file.file_stem().and_then(OsStr::to_str) {
-            map.insert(name.to_owned(), file);
+        let Ok(relative_path) = file
+            .with_extension("")
+            .strip_prefix(&test_dir)
+            .map(Path::to_path_buf)
+        else {
+            continue;
+        };
+
+        let name_parts = relative_path
+            .components()
+            .map(|component| component.as_os_str().to_str())
+            .collect::<Option<Vec<_>>>();
+
+        if let Some(name_parts) = name_parts {
+            map.insert(name_parts.join("/"), file);
         }
     }
 
@@ -186,6 +229,7 @@ pub fn get_test_cases(solution_dir: impl AsRef<Path>) -> crate::Result<Vec<FileT
         .into_iter()
         .filter_map(|(name, input_file)| {
             answer_files.remove(&name).map(|answer_file| FileTestCase {
+                group: name.rsplit_once('/').map(|(group, _)| group.to_string()),
                 name,
                 input_file,
                 answer_file,
@@ -195,3 +239,19 @@ pub fn get_test_cases(solution_dir: impl AsRef<Path>) -> crate::Result<Vec<FileT
 
     Ok(test_files)
 }
+
+fn collect_files(dir: &Path, files: &mut Vec<PathBuf>) -> crate::Result<()> {
+    for entry in dir.read_dir()?.filter_map(Result::ok) {
+        let Ok(file_type) = entry.file_type() else {
+            continue;
+        };
+
+        if file_type.is_dir() {
+            collect_files(&entry.path(), files)?;
+        } else if file_type.is_file() {
+            files.push(entry.path());
+        }
+    }
+
+    Ok(())
+}
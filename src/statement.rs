@@ -0,0 +1,359 @@
+use colored::Colorize;
+use regex::{Captures, Regex};
+use scraper::{node::Node, ElementRef, Html, Selector};
+
+/// The name of the file in a solution folder that contains the problem
+/// statement.
+pub const STATEMENT_FILE_NAME: &str = "problem.md";
+
+const BLOCK_ELEMENTS: &[&str] = &[
+    "blockquote",
+    "center",
+    "div",
+    "figcaption",
+    "figure",
+    "h1",
+    "h2",
+    "h3",
+    "h4",
+    "h5",
+    "h6",
+    "hr",
+    "ol",
+    "p",
+    "pre",
+    "section",
+    "table",
+    "ul",
+];
+
+/// Converts the statement on a Kattis problem page to Markdown. Math is kept as
+/// TeX between dollar signs, and the sample tables are turned into code blocks.
+/// Relative links are resolved against `base_url`. Returns `None` if the page
+/// has no statement.
+pub fn statement_to_markdown(page_html: &str, base_url: &str) -> Option<String> {
+    let page = Html::parse_document(page_html);
+    let body = page
+        .select(&Selector::parse(".problembody").unwrap())
+        .next()?;
+
+    let converter = MarkdownConverter { base_url };
+    let mut blocks = Vec::new();
+
+    let title = page
+        .select(&Selector::parse("h1.book-page-heading").unwrap())
+        .chain(page.select(&Selector::parse("h1").unwrap()))
+        .next();
+
+    if let Some(title) = title {
+        blocks.push(format!("# {}", converter.inline_children(title).trim()));
+    }
+
+    blocks.extend(converter.blocks(body));
+
+    Some(format!("{}\n", blocks.join("\n\n")))
+}
+
+struct MarkdownConverter<'a> {
+    base_url: &'a str,
+}
+
+impl MarkdownConverter<'_> {
+    /// Converts the children of an element to Markdown blocks. Inline content
+    /// between block elements is gathered into paragraphs.
+    fn blocks(&self, element: ElementRef) -> Vec<String> {
+        let mut blocks = Vec::new();
+        let mut paragraph = String::new();
+
+        fn end_paragraph(paragraph: &mut String, blocks: &mut Vec<String>) {
+            if !paragraph.trim().is_empty() {
+                blocks.push(paragraph.trim().to_string());
+            }
+
+            paragraph.clear();
+        }
+
+        for child in element.children() {
+            match ElementRef::wrap(child) {
+                Some(child) if is_block(child) => {
+                    end_paragraph(&mut paragraph, &mut blocks);
+                    blocks.extend(self.block(child));
+                }
+                Some(child) => paragraph.push_str(&self.inline(child)),
+                None => {
+                    if let Node::Text(text) = child.value() {
+                        paragraph.push_str(&inline_text(text));
+                    }
+                }
+            }
+        }
+
+        end_paragraph(&mut paragraph, &mut blocks);
+
+        blocks
+    }
+
+    fn block(&self, element: ElementRef) -> Vec<String> {
+        let name = element.value().name();
+
+        let block = match name {
+            "h1" | "h2" | "h3" | "h4" | "h5" | "h6" => {
+                let level = name[1..].parse().unwrap_or(1);
+                format!(
+                    "{} {}",
+                    "#".repeat(level),
+                    self.inline_children(element).trim()
+                )
+            }
+            "p" => self.inline_children(element).trim().to_string(),
+            "pre" => code_block(&element.text().collect::<String>()),
+            "hr" => "---".to_string(),
+            "ul" | "ol" => self.list(element, name == "ol"),
+            "blockquote" => prefix_lines(&self.blocks(element).join("\n\n"), "> ", "> "),
+            "figcaption" => format!("*{}*", self.inline_children(element).trim()),
+            "table" if has_class(element, "sample") => return self.sample_table(element),
+            "table" => self.table(element),
+            _ => return self.blocks(element),
+        };
+
+        if block.trim().is_empty() {
+            Vec::new()
+        } else {
+            vec![block]
+        }
+    }
+
+    fn inline(&self, element: ElementRef) -> String {
+        let content = || self.inline_children(element);
+
+        match element.value().name() {
+            "em" | "i" => surround(&content(), "*"),
+            "strong" | "b" => surround(&content(), "**"),
+            "code" | "tt" | "kbd" => surround(&element.text().collect::<String>(), "`"),
+            "br" => "  \n".to_string(),
+            "img" => {
+                // Kattis uses the TeX source of the figure as alt text.
+                let alt = element
+                    .value()
+                    .attr("alt")
+                    .filter(|alt| !alt.starts_with('\\'))
+                    .unwrap_or("image")
+                    .replace(['[', ']'], "");
+                let src = element.value().attr("src").unwrap_or_default();
+                format!("![{alt}]({})", self.absolute_url(src))
+            }
+            "a" => match element.value().attr("href") {
+                Some(href) => format!("[{}]({})", content().trim(), self.absolute_url(href)),
+                None => content(),
+            },
+            "script" if element.value().attr("type") == Some("math/tex") => {
+                format!("${}$", element.text().collect::<String>())
+            }
+            "script" | "style" => String::new(),
+            _ => content(),
+        }
+    }
+
+    fn inline_children(&self, element: ElementRef) -> String {
+        element
+            .children()
+            .map(|child| match ElementRef::wrap(child) {
+                Some(child) if is_block(child) => format!(" {} ", self.blocks(child).join(" ")),
+                Some(child) => self.inline(child),
+                None => match child.value() {
+                    Node::Text(text) => inline_text(text),
+                    _ => String::new(),
+                },
+            })
+            .collect()
+    }
+
+    fn list(&self, element: ElementRef, is_ordered: bool) -> String {
+        element
+            .children()
+            .filter_map(ElementRef::wrap)
+            .filter(|child| child.value().name() == "li")
+            .enumerate()
+            .map(|(i, item)| {
+                let marker = if is_ordered {
+                    format!("{}. ", i + 1)
+                } else {
+                    "- ".to_string()
+                };
+
+                let indent = " ".repeat(marker.len());
+                prefix_lines(&self.blocks(item).join("\n"), &marker, &indent)
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    /// Kattis shows samples in tables with a header row naming the samples
+    /// followed by a row with their contents.
+    fn sample_table(&self, element: ElementRef) -> Vec<String> {
+        let header_selector = Selector::parse("th").unwrap();
+        let cell_selector = Selector::parse("td").unwrap();
+
+        element
+            .select(&header_selector)
+            .zip(element.select(&cell_selector))
+            .flat_map(|(header, cell)| {
+                let contents = match cell.select(&Selector::parse("pre").unwrap()).next() {
+                    Some(pre) => pre.text().collect::<String>(),
+                    None => cell.text().collect::<String>(),
+                };
+
+                [
+                    format!("### {}", self.inline_children(header).trim()),
+                    code_block(&contents),
+                ]
+            })
+            .collect()
+    }
+
+    fn table(&self, element: ElementRef) -> String {
+        let rows = element
+            .select(&Selector::parse("tr").unwrap())
+            .map(|row| {
+                row.children()
+                    .filter_map(ElementRef::wrap)
+                    .filter(|cell| matches!(cell.value().name(), "th" | "td"))
+                    .map(|cell| self.inline_children(cell).trim().replace('|', "\\|"))
+                    .collect::<Vec<_>>()
+            })
+            .filter(|row| !row.is_empty())
+            .collect::<Vec<_>>();
+
+        let Some(num_columns) = rows.iter().map(Vec::len).max() else {
+            return String::new();
+        };
+
+        let mut lines = rows
+            .iter()
+            .map(|row| format!("| {} |", row.join(" | ")))
+            .collect::<Vec<_>>();
+
+        lines.insert(1, format!("|{}", " --- |".repeat(num_columns)));
+
+        lines.join("\n")
+    }
+
+    fn absolute_url(&self, url: &str) -> String {
+        if url.starts_with('/') {
+            format!("{}{url}", self.base_url.trim_end_matches('/'))
+        } else {
+            url.to_string()
+        }
+    }
+}
+
+fn is_block(element: ElementRef) -> bool {
+    BLOCK_ELEMENTS.contains(&element.value().name())
+}
+
+fn has_class(element: ElementRef, class: &str) -> bool {
+    element.value().classes().any(|c| c == class)
+}
+
+/// Collapses whitespace like a browser does and uses dollar signs for math.
+fn inline_text(text: &str) -> String {
+    let collapsed = Regex::new(r"\s+").unwrap().replace_all(text, " ");
+
+    collapsed
+        .replace(r"\(", "$")
+        .replace(r"\)", "$")
+        .replace(r"\[", "$$")
+        .replace(r"\]", "$$")
+}
+
+fn surround(text: &str, marker: &str) -> String {
+    let trimmed = text.trim();
+
+    if trimmed.is_empty() {
+        return text.to_string();
+    }
+
+    // Whitespace is moved outside of the markers since Markdown does not allow
+    // it right inside them.
+    let leading = &text[..text.len() - text.trim_start().len()];
+    let trailing = &text[text.trim_end().len()..];
+
+    format!("{leading}{marker}{trimmed}{marker}{trailing}")
+}
+
+fn code_block(contents: &str) -> String {
+    format!("```\n{}\n```", contents.trim_end_matches(['\n', '\r']))
+}
+
+fn prefix_lines(text: &str, first_prefix: &str, prefix: &str) -> String {
+    text.lines()
+        .enumerate()
+        .map(|(i, line)| {
+            let prefix = if i == 0 { first_prefix } else { prefix };
+            format!("{prefix}{line}").trim_end().to_string()
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Formats a Markdown statement for the terminal. Headings and emphasis are
+/// coloured, and code blocks are indented such that samples can be copied as
+/// they are.
+pub fn render_markdown(markdown: &str) -> String {
+    let mut in_code_block = false;
+    let mut lines = Vec::new();
+
+    for line in markdown.lines() {
+        if line.starts_with("```") {
+            in_code_block = !in_code_block;
+            continue;
+        }
+
+        if in_code_block {
+            lines.push(format!("    {line}"));
+            continue;
+        }
+
+        let heading_level = line.chars().take_while(|&c| c == '#').count();
+
+        let rendered = match line[heading_level..].strip_prefix(' ') {
+            Some(heading) if heading_level == 1 => {
+                heading.to_uppercase().bright_cyan().bold().to_string()
+            }
+            Some(heading) if heading_level > 1 => heading.bright_cyan().to_string(),
+            _ => render_inline(line),
+        };
+
+        lines.push(rendered);
+    }
+
+    lines.join("\n")
+}
+
+type Replacement = fn(&Captures) -> String;
+
+fn render_inline(line: &str) -> String {
+    let replacements: [(&str, Replacement); 5] = [
+        (r"!\[([^\]]*)\]\(([^)]*)\)", |c| match &c[1] {
+            "" | "image" => format!("[image] {}", c[2].underline()),
+            alt => format!("[image: {alt}] {}", c[2].underline()),
+        }),
+        (r"\[([^\]]*)\]\(([^)]*)\)", |c| {
+            format!("{} ({})", &c[1], c[2].underline())
+        }),
+        (r"`([^`]+)`", |c| c[1].bright_yellow().to_string()),
+        (r"\*\*([^*]+)\*\*", |c| c[1].bold().to_string()),
+        (r"\*([^*]+)\*", |c| c[1].italic().to_string()),
+    ];
+
+    let mut line = line.trim_end().to_string();
+
+    for (pattern, replacement) in replacements {
+        line = Regex::new(pattern)
+            .unwrap()
+            .replace_all(&line, replacement)
+            .into_owned();
+    }
+
+    line
+}
@@ -0,0 +1,285 @@
+use std::{collections::HashSet, ffi::OsStr, path::Path};
+
+use eyre::Context;
+use regex::Regex;
+use reqwest::StatusCode;
+use scraper::{ElementRef, Html, Selector};
+
+use crate::App;
+
+/// Stops looking for more matching submissions after this many pages, such
+/// that looking for something rare does not go through your entire history.
+const MAX_PAGES_TO_FETCH: usize = 20;
+
+/// A submission as listed on your submissions page on Kattis.
+#[derive(Debug)]
+pub struct SubmissionSummary {
+    pub id: String,
+    /// The ID of the problem, if it could be found in the link to it.
+    pub problem_id: Option<String>,
+    pub problem_name: String,
+    pub language: String,
+    pub verdict: String,
+    pub cpu_time: Option<String>,
+    /// When the submission was made, as shown by Kattis.
+    pub submitted_at: String,
+}
+
+impl SubmissionSummary {
+    pub fn is_accepted(&self) -> bool {
+        self.verdict.eq_ignore_ascii_case("accepted")
+    }
+
+    pub fn is_for_problem(&self, problem_id: &str) -> bool {
+        self.problem_id
+            .as_deref()
+            .is_some_and(|id| id.eq_ignore_ascii_case(problem_id))
+    }
+}
+
+pub fn make_submission_url(app: &App, submission_id: &str) -> crate::Result<String> {
+    Ok(format!(
+        "{}/{submission_id}",
+        app.config.try_kattisrc()?.kattis.submissions_url,
+    ))
+}
+
+/// A file submitted to Kattis.
+#[derive(Debug)]
+pub struct SourceFile {
+    pub name: String,
+    pub contents: Vec<u8>,
+}
+
+/// The source code of a submission along with the problem it was made to.
+#[derive(Debug)]
+pub struct SubmissionSource {
+    pub problem_id: Option<String>,
+    pub files: Vec<SourceFile>,
+}
+
+/// Downloads the files of one of your submissions. You must be logged in.
+pub async fn fetch_submission_source(
+    app: &App,
+    submission_id: &str,
+) -> crate::Result<SubmissionSource> {
+    let host_name = &app.config.try_kattisrc()?.kattis.host_name;
+    let submission_url = make_submission_url(app, submission_id)?;
+
+    let res = app
+        .client
+        .get(&submission_url)
+        .send()
+        .await
+        .wrap_err("Failed to send request to Kattis")?;
+
+    match res.status() {
+        status if status.is_success() => {}
+        StatusCode::NOT_FOUND => eyre::bail!("Submission {submission_id} does not exist"),
+        status => {
+            eyre::bail!("Failed to get submission from Kattis (http status code: {status})")
+        }
+    }
+
+    let page_html = res
+        .text()
+        .await
+        .wrap_err("Failed to read submission from Kattis")?;
+
+    let (problem_id, file_links) = parse_submission_page(&page_html);
+
+    eyre::ensure!(
+        !file_links.is_empty(),
+        "Could not find the source code of submission {submission_id}. You can only download your own submissions."
+    );
+
+    let mut files = Vec::new();
+
+    for (name, href) in file_links {
+        let url = if href.starts_with('/') {
+            format!("https://{host_name}{href}")
+        } else {
+            href
+        };
+
+        let res = app
+            .client
+            .get(&url)
+            .send()
+            .await
+            .wrap_err("Failed to send request to Kattis")?;
+
+        if !res.status().is_success() {
+            eyre::bail!(
+                "Failed to download {name} from Kattis (http status code: {})",
+                res.status()
+            )
+        }
+
+        let contents = res
+            .bytes()
+            .await
+            .wrap_err_with(|| format!("Failed to read {name} from Kattis"))?
+            .to_vec();
+
+        files.push(SourceFile { name, contents });
+    }
+
+    Ok(SubmissionSource { problem_id, files })
+}
+
+/// Finds the problem ID and the links to download the source files on a
+/// submission's page. The links are given along with the names of the files.
+fn parse_submission_page(page_html: &str) -> (Option<String>, Vec<(String, String)>) {
+    let page = Html::parse_document(page_html);
+    let link_selector = Selector::parse("a[href]").unwrap();
+    let source_link = Regex::new(r"/submissions/\d+/source/([^/?#]+)(?:[?#].*)?$").unwrap();
+    let problem_link = Regex::new(r"/problems/([\w.]+)/?$").unwrap();
+
+    let hrefs = page
+        .select(&link_selector)
+        .filter_map(|link| link.value().attr("href"))
+        .collect::<Vec<_>>();
+
+    let problem_id = hrefs
+        .iter()
+        .find_map(|href| problem_link.captures(href))
+        .map(|captures| captures[1].to_string());
+
+    let mut file_links: Vec<(String, String)> = Vec::new();
+
+    for href in hrefs {
+        let Some(captures) = source_link.captures(href) else {
+            continue;
+        };
+
+        // File names come from Kattis, but they must not be able to point
+        // outside of the solution folder on any platform, so backslashes and
+        // drive letters are not allowed either.
+        let name = captures[1].to_string();
+        let is_plain_file_name =
+            Path::new(&name).file_name() == Some(OsStr::new(&name)) && !name.contains(['\\', ':']);
+        let is_new = !file_links.iter().any(|(seen, _)| *seen == name);
+
+        if is_new && is_plain_file_name {
+            file_links.push((name, href.to_string()));
+        }
+    }
+
+    (problem_id, file_links)
+}
+
+/// Goes through your submissions, newest first, until `num_wanted` of them
+/// match. Fewer are returned if you do not have that many. You must be logged
+/// in.
+pub async fn find_submissions(
+    app: &App,
+    num_wanted: usize,
+    is_match: impl Fn(&SubmissionSummary) -> bool,
+) -> crate::Result<Vec<SubmissionSummary>> {
+    let mut matching = Vec::new();
+    let mut seen_ids = HashSet::new();
+
+    for page in 0..MAX_PAGES_TO_FETCH {
+        let submissions = fetch_submissions_page(app, page).await?;
+
+        // Kattis shows the last page again when asking for one past the end.
+        let new_submissions = submissions
+            .into_iter()
+            .filter(|submission| seen_ids.insert(submission.id.clone()))
+            .collect::<Vec<_>>();
+
+        if new_submissions.is_empty() {
+            break;
+        }
+
+        matching.extend(new_submissions.into_iter().filter(&is_match));
+
+        if matching.len() >= num_wanted {
+            matching.truncate(num_wanted);
+            break;
+        }
+    }
+
+    Ok(matching)
+}
+
+/// Downloads a page of your submissions, newest first. Pages are numbered from
+/// 0. You must be logged in.
+async fn fetch_submissions_page(app: &App, page: usize) -> crate::Result<Vec<SubmissionSummary>> {
+    let submissions_url = &app.config.try_kattisrc()?.kattis.submissions_url;
+
+    let res = app
+        .client
+        .get(submissions_url)
+        .query(&[("page", page)])
+        .send()
+        .await
+        .wrap_err("Failed to send request to Kattis")?;
+
+    if !res.status().is_success() {
+        eyre::bail!(
+            "Failed to get submissions from Kattis (http status code: {})",
+            res.status()
+        )
+    }
+
+    let page_html = res
+        .text()
+        .await
+        .wrap_err("Failed to read submissions from Kattis")?;
+
+    Ok(parse_submissions(&page_html))
+}
+
+/// Reads the submissions from the table on a submissions page. Rows are
+/// recognised by their submission ID, and the columns by their `data-type`.
+fn parse_submissions(page_html: &str) -> Vec<SubmissionSummary> {
+    let page = Html::parse_document(page_html);
+    let row_selector = Selector::parse("table tr").unwrap();
+    let link_selector = Selector::parse("a[href]").unwrap();
+    let submission_link = Regex::new(r"/submissions/(\d+)/?$").unwrap();
+    let problem_link = Regex::new(r"/problems/([\w.]+)/?$").unwrap();
+
+    let find_link = |element: ElementRef, regex: &Regex| {
+        element
+            .select(&link_selector)
+            .filter_map(|link| link.value().attr("href"))
+            .find_map(|href| regex.captures(href))
+            .map(|captures| captures[1].to_string())
+    };
+
+    page.select(&row_selector)
+        .filter_map(|row| {
+            let id = row
+                .value()
+                .attr("data-submission-id")
+                .map(str::to_string)
+                .or_else(|| find_link(row, &submission_link))?;
+
+            let cell = |data_type: &str| {
+                let selector = Selector::parse(&format!(r#"td[data-type="{data_type}"]"#)).unwrap();
+                row.select(&selector).next()
+            };
+            let cell_text = |data_type: &str| {
+                cell(data_type)
+                    .map(|cell| collapse_whitespace(&cell.text().collect::<String>()))
+                    .unwrap_or_default()
+            };
+
+            Some(SubmissionSummary {
+                id,
+                problem_id: cell("problem").and_then(|cell| find_link(cell, &problem_link)),
+                problem_name: cell_text("problem"),
+                language: cell_text("lang"),
+                verdict: cell_text("status"),
+                cpu_time: Some(cell_text("cpu")).filter(|time| !time.is_empty()),
+                submitted_at: cell_text("time"),
+            })
+        })
+        .collect()
+}
+
+fn collapse_whitespace(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
@@ -0,0 +1,190 @@
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+/// The values of the variables that can be used in templates. Variables can be
+/// known without having a value, in which case they are treated as empty.
+#[derive(Debug, Default)]
+pub struct TemplateVariables {
+    values: BTreeMap<String, Option<String>>,
+}
+
+impl TemplateVariables {
+    pub fn set(&mut self, name: &str, value: Option<String>) -> &mut Self {
+        self.values.insert(name.to_string(), value);
+        self
+    }
+
+    fn get(&self, name: &str) -> Option<Option<&str>> {
+        self.values.get(name).map(Option::as_deref)
+    }
+}
+
+/// The result of filling in a template.
+#[derive(Debug)]
+pub struct RenderedTemplate {
+    pub text: String,
+    /// Variables used in the template that kitty does not know. They are left
+    /// as they are in the text.
+    pub unknown_variables: BTreeSet<String>,
+}
+
+/// Fills in the variables of a template. The following forms are supported:
+///
+///  - `$NAME` and `${NAME}` are replaced with the value of the variable.
+///  - `${NAME:-default}` is replaced with the default if the variable is empty.
+///  - `${NAME:+text}` is replaced with the text if the variable is not empty.
+///
+/// Variable names consist of uppercase letters, digits and underscores, and
+/// the default and text may contain variables themselves. Everything else,
+/// including `$$` and unknown variables in any of the forms, is copied as it
+/// is, such that shell scripts and Makefiles in templates are left intact.
+pub fn render(template: &str, variables: &TemplateVariables) -> RenderedTemplate {
+    let mut rendered = RenderedTemplate {
+        text: String::with_capacity(template.len()),
+        unknown_variables: BTreeSet::new(),
+    };
+
+    render_into(template, variables, &mut rendered);
+
+    rendered
+}
+
+fn render_into(template: &str, variables: &TemplateVariables, rendered: &mut RenderedTemplate) {
+    let mut rest = template;
+
+    while let Some(dollar) = rest.find('$') {
+        rendered.text.push_str(&rest[..dollar]);
+        rest = &rest[dollar..];
+
+        if let Some(after) = rest.strip_prefix("$$") {
+            rendered.text.push_str("$$");
+            rest = after;
+        } else if let Some((expression, after)) = braced_expression(rest) {
+            render_expression(expression, variables, rendered);
+            rest = after;
+        } else if let Some(name) = variable_name(&rest[1..]) {
+            match variables.get(name) {
+                Some(value) => rendered.text.push_str(value.unwrap_or_default()),
+                None => {
+                    rendered.text.push_str(&rest[..=name.len()]);
+                    rendered.unknown_variables.insert(name.to_string());
+                }
+            }
+
+            rest = &rest[1 + name.len()..];
+        } else {
+            rendered.text.push('$');
+            rest = &rest[1..];
+        }
+    }
+
+    rendered.text.push_str(rest);
+}
+
+/// Renders the contents of a `${...}` expression. The expression is kept as it
+/// is if it is not valid or its variable is unknown.
+fn render_expression(
+    expression: &str,
+    variables: &TemplateVariables,
+    rendered: &mut RenderedTemplate,
+) {
+    let name = variable_name(expression).unwrap_or_default();
+    let operation = &expression[name.len()..];
+
+    let is_valid = !name.is_empty()
+        && (operation.is_empty() || operation.starts_with(":-") || operation.starts_with(":+"));
+
+    if !is_valid {
+        rendered.text.push_str(&format!("${{{expression}}}"));
+        return;
+    }
+
+    let Some(value) = variables.get(name) else {
+        rendered.unknown_variables.insert(name.to_string());
+        rendered.text.push_str(&format!("${{{expression}}}"));
+        return;
+    };
+
+    let value = value.filter(|value| !value.is_empty());
+
+    if let Some(default) = operation.strip_prefix(":-") {
+        match value {
+            Some(value) => rendered.text.push_str(value),
+            None => render_into(default, variables, rendered),
+        }
+    } else if let Some(text) = operation.strip_prefix(":+") {
+        if value.is_some() {
+            render_into(text, variables, rendered);
+        }
+    } else {
+        rendered.text.push_str(value.unwrap_or_default());
+    }
+}
+
+/// Splits `${...}` at the start of the text into its contents and the text
+/// after it. Nested expressions are allowed.
+fn braced_expression(text: &str) -> Option<(&str, &str)> {
+    let contents = text.strip_prefix("${")?;
+    let mut depth = 0;
+    let mut previous = None;
+
+    for (i, c) in contents.char_indices() {
+        match c {
+            '{' if previous == Some('$') => depth += 1,
+            '}' if depth == 0 => return Some((&contents[..i], &contents[i + 1..])),
+            '}' => depth -= 1,
+            _ => {}
+        }
+
+        previous = Some(c);
+    }
+
+    None
+}
+
+/// The variable name at the start of the text, if any.
+fn variable_name(text: &str) -> Option<&str> {
+    let starts_correctly = text
+        .chars()
+        .next()
+        .is_some_and(|c| c.is_ascii_uppercase() || c == '_');
+
+    if !starts_correctly {
+        return None;
+    }
+
+    let len = text
+        .find(|c: char| !(c.is_ascii_uppercase() || c.is_ascii_digit() || c == '_'))
+        .unwrap_or(text.len());
+
+    Some(&text[..len])
+}
+
+/// Today's date in UTC, formatted as YYYY-MM-DD.
+pub fn today() -> String {
+    let days_since_epoch = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_secs() / (24 * 60 * 60))
+        .unwrap_or_default() as i64;
+
+    // Converts days to a date in the proleptic Gregorian calendar, see
+    // http://howardhinnant.github.io/date_algorithms.html#civil_from_days
+    let z = days_since_epoch + 719_468;
+    let era = z.div_euclid(146_097);
+    let day_of_era = z.rem_euclid(146_097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let shifted_month = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * shifted_month + 2) / 5 + 1;
+    let month = if shifted_month < 10 {
+        shifted_month + 3
+    } else {
+        shifted_month - 9
+    };
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+
+    format!("{year:04}-{month:02}-{day:02}")
+}
@@ -1,15 +1,22 @@
 use std::{
+    fmt,
     fs::File,
-    io::{self, Read},
-    path::PathBuf,
-    process::{self, Command, Stdio},
+    io::{self, Read, Write},
+    path::{Path, PathBuf},
+    process::{self, Child, ChildStderr, ChildStdin, ChildStdout, Command, ExitStatus, Stdio},
+    sync::Mutex,
+    thread,
     time::{Duration, Instant},
 };
 
 use colored::Colorize;
 use eyre::{bail, Context};
 
-use crate::App;
+use crate::{
+    diff::{self, Diff},
+    validator::{CustomValidator, Judgement, OutputValidator},
+    App,
+};
 
 pub trait TestCaseIO {
     type Input<'a>
@@ -28,9 +35,76 @@ pub trait TestCaseIO {
 
 pub type TestCaseResult = Result<TestCaseInfo, TestCaseError>;
 
+/// How often a running solution is polled to check whether it has exceeded
+/// its time limit.
+const TIME_LIMIT_POLL_INTERVAL: Duration = Duration::from_millis(5);
+
+/// How long the interactor may keep running after the solution has exited.
+const INTERACTOR_EXIT_GRACE_PERIOD: Duration = Duration::from_secs(5);
+
+pub const BYTES_PER_MEGABYTE: u64 = 1024 * 1024;
+
+/// The address space cap applied to a solution is this many times larger than
+/// its memory limit. The cap only exists to stop runaway solutions, since
+/// runtimes tend to reserve much more virtual memory than they actually use.
+/// Whether the memory limit was exceeded is decided by the peak resident memory.
+#[cfg(target_os = "linux")]
+const ADDRESS_SPACE_HEADROOM_FACTOR: u64 = 2;
+
+/// Messages that common runtimes print when an allocation fails. Solutions hit
+/// by the address space cap crash before their resident memory exceeds the
+/// limit, so these are used to recognise them. They are only trusted if the
+/// solution used at least half its memory limit, which solutions that grow
+/// their memory step by step reach before the cap stops them. Otherwise,
+/// programs that merely print such a message would be judged as exceeding the
+/// memory limit.
+const OUT_OF_MEMORY_MESSAGES: &[&str] = &[
+    "MemoryError",
+    "std::bad_alloc",
+    "OutOfMemoryError",
+    "memory allocation of",
+    "out of memory",
+];
+
+#[derive(Debug, Default, Clone, Copy)]
+pub struct ResourceLimits {
+    pub time: Option<Duration>,
+    /// The memory limit in bytes.
+    pub memory: Option<u64>,
+}
+
+#[derive(Debug)]
+pub struct ExecutionOutput {
+    pub output: process::Output,
+    pub running_time: Duration,
+    pub time_limit_exceeded: bool,
+    /// The peak resident memory of the program in bytes, if it could be
+    /// measured on this platform.
+    pub peak_memory: Option<u64>,
+}
+
+#[derive(Debug)]
+pub struct InteractiveExecutionOutput {
+    /// The stdout of each program holds everything it sent to the other.
+    pub solution: ExecutionOutput,
+    pub interactor: process::Output,
+    pub transcript: Transcript,
+}
+
+/// Describes how to run a solution and judge its output.
+#[derive(Debug)]
+pub struct TestSetup<'a> {
+    pub run_cmd: &'a [String],
+    pub limits: ResourceLimits,
+    pub validator: &'a OutputValidator,
+    /// For interactive problems, the interactor communicates with the solution
+    /// while it runs and judges it instead of the validator.
+    pub interactor: Option<&'a CustomValidator>,
+}
+
 pub fn run_test<'a, T: TestCaseIO + 'a>(
     app: &App,
-    run_cmd: &[String],
+    setup: &TestSetup,
     test_case: &'a T,
 ) -> crate::Result<TestCaseResult>
 where
@@ -44,31 +118,175 @@ where
     let expected_answer =
         io::read_to_string(expected_answer).wrap_err("Failed to load expected answer")?;
 
-    let start_time = Instant::now();
-    let output = run_with_input(app, run_cmd, &mut input.as_bytes())?;
-    let running_time = start_time.elapsed();
+    if let Some(interactor) = setup.interactor {
+        return run_interactive_test(app, setup, interactor, input, expected_answer);
+    }
+
+    let execution =
+        run_with_input_and_limits(app, setup.run_cmd, &mut input.as_bytes(), setup.limits)?;
+
+    let (input, info) = match check_execution(setup, execution, input) {
+        Ok(checked) => checked,
+        Err(outcome) => return Ok(outcome),
+    };
+
+    let judgement = setup
+        .validator
+        .judge(app, &input, &expected_answer, &info.stdout)?;
+
+    if let Judgement::Rejected { feedback } = judgement {
+        return Ok(Err(TestCaseError::WrongAnswer {
+            input,
+            expected: expected_answer.trim_end().to_string(),
+            actual: info.stdout.trim_end().to_string(),
+            saved_output: None,
+            stderr: info.stderr.trim_end().to_string(),
+            feedback,
+            running_time: info.running_time,
+        }));
+    }
+
+    Ok(Ok(info))
+}
+
+fn run_interactive_test(
+    app: &App,
+    setup: &TestSetup,
+    interactor: &CustomValidator,
+    input: String,
+    expected_answer: String,
+) -> crate::Result<TestCaseResult> {
+    let interactor_run = interactor.prepare(&input, &expected_answer)?;
+
+    let InteractiveExecutionOutput {
+        solution,
+        interactor: interactor_output,
+        transcript,
+    } = run_interactive(app, setup.run_cmd, interactor_run.cmd(), setup.limits)?;
+
+    let judgement = interactor_run
+        .judgement(interactor_output.status)?
+        .ok_or_else(|| interactor_run.unexpected_exit_error("interactor", &interactor_output))?;
+
+    let (input, info) = match check_execution(setup, solution, input) {
+        Ok(checked) => checked,
+        // A solution often crashes when the interactor stops talking to it, so
+        // the interactor's verdict takes precedence, like on Kattis.
+        Err(Err(TestCaseError::RuntimeError {
+            input,
+            stdout,
+            stderr,
+            running_time,
+        })) if matches!(judgement, Judgement::Rejected { .. }) => (
+            input,
+            TestCaseInfo {
+                running_time,
+                peak_memory: None,
+                stdout,
+                stderr,
+            },
+        ),
+        Err(outcome) => return Ok(outcome),
+    };
+
+    match judgement {
+        Judgement::Accepted => Ok(Ok(info)),
+        Judgement::Rejected { feedback } => Ok(Err(TestCaseError::WrongInteraction {
+            input,
+            transcript: transcript.to_string(),
+            stderr: info.stderr.trim_end().to_string(),
+            feedback,
+            running_time: info.running_time,
+        })),
+    }
+}
+
+/// Saves a copy of an output that is too large to be shown in the terminal,
+/// returning the path of the file.
+fn save_full_output(output: &str) -> crate::Result<PathBuf> {
+    let mut file = tempfile::Builder::new()
+        .prefix("kitty-output-")
+        .suffix(".out")
+        .tempfile()
+        .wrap_err("Failed to create file for the full output")?;
+
+    writeln!(file, "{output}").wrap_err("Failed to save the full output")?;
+
+    let (_, path) = file
+        .keep()
+        .wrap_err("Failed to keep the file with the full output")?;
+
+    Ok(path)
+}
+
+/// Checks that the program ran without exceeding its limits or crashing. On
+/// success, the input and the running information are returned.
+#[allow(clippy::result_large_err)]
+fn check_execution(
+    setup: &TestSetup,
+    execution: ExecutionOutput,
+    input: String,
+) -> Result<(String, TestCaseInfo), TestCaseResult> {
+    let ExecutionOutput {
+        output,
+        running_time,
+        time_limit_exceeded,
+        peak_memory,
+    } = execution;
 
     let stdout = String::from_utf8_lossy(&output.stdout).to_string();
     let stderr = String::from_utf8_lossy(&output.stderr).to_string();
 
-    if !output.status.success() {
-        return Ok(Err(TestCaseError::RuntimeError {
+    if time_limit_exceeded {
+        return Err(Err(TestCaseError::TimeLimitExceeded {
             input,
+            elapsed: running_time,
+            time_limit: setup.limits.time.unwrap_or_default(),
             stdout,
             stderr,
         }));
     }
 
-    if !are_equal_after_normalisation(&stdout, &expected_answer) {
-        return Ok(Err(TestCaseError::WrongAnswer {
+    if let Some(memory_limit) = setup.limits.memory {
+        let exceeded_limit = peak_memory.is_some_and(|peak| peak > memory_limit);
+        let used_much_memory = peak_memory.is_some_and(|peak| peak >= memory_limit / 2);
+        let failed_to_allocate = cfg!(target_os = "linux")
+            && used_much_memory
+            && !output.status.success()
+            && OUT_OF_MEMORY_MESSAGES
+                .iter()
+                .any(|message| stderr.contains(message));
+
+        if exceeded_limit || failed_to_allocate {
+            return Err(Err(TestCaseError::MemoryLimitExceeded {
+                input,
+                peak_memory,
+                memory_limit,
+                stdout,
+                stderr,
+                running_time,
+            }));
+        }
+    }
+
+    if !output.status.success() {
+        return Err(Err(TestCaseError::RuntimeError {
             input,
-            expected: expected_answer.trim_end().to_string(),
-            actual: stdout.trim_end().to_string(),
-            stderr: stderr.trim_end().to_string(),
+            stdout,
+            stderr,
+            running_time,
         }));
     }
 
-    Ok(Ok(TestCaseInfo { running_time }))
+    Ok((
+        input,
+        TestCaseInfo {
+            running_time,
+            peak_memory,
+            stdout,
+            stderr,
+        },
+    ))
 }
 
 pub fn run_with_input(
@@ -76,6 +294,256 @@ pub fn run_with_input(
     run_cmd: &[String],
     input: &mut impl Read,
 ) -> crate::Result<process::Output> {
+    run_with_input_and_limits(app, run_cmd, input, ResourceLimits::default())
+        .map(|execution| execution.output)
+}
+
+/// Runs the given command with `input` piped into its stdin. If the program
+/// runs for longer than the time limit, it is killed. On Linux, the program's
+/// address space is capped when a memory limit is given.
+pub fn run_with_input_and_limits(
+    app: &App,
+    run_cmd: &[String],
+    input: &mut impl Read,
+    limits: ResourceLimits,
+) -> crate::Result<ExecutionOutput> {
+    let mut child = spawn_with_limits(app, run_cmd, limits)?;
+    let (mut child_stdin, mut child_stdout, mut child_stderr) = take_pipes(&mut child)?;
+
+    let mut input_bytes = Vec::new();
+    input
+        .read_to_end(&mut input_bytes)
+        .wrap_err("Failed to read test case input")?;
+
+    let start_time = Instant::now();
+
+    // Input is written and output is read on separate threads such that the
+    // time limit can be enforced even if the solution never reads its input or
+    // fills up the output pipes.
+    thread::scope(|scope| {
+        let input_writer = scope.spawn(move || {
+            let result = child_stdin.write_all(&input_bytes);
+
+            // Manually drop stdin to ensure that EOF is sent. If this is not
+            // done, the child process might not terminate if it reads until EOF.
+            drop(child_stdin);
+
+            match result {
+                // The solution is allowed to exit without reading all input.
+                Err(e) if e.kind() == io::ErrorKind::BrokenPipe => Ok(()),
+                result => result,
+            }
+        });
+        let stdout_reader = scope.spawn(move || read_all(&mut child_stdout));
+        let stderr_reader = scope.spawn(move || read_all(&mut child_stderr));
+
+        let ChildExit {
+            status,
+            time_limit_exceeded,
+            peak_memory,
+        } = wait_with_time_limit(&mut child, limits.time).wrap_err("Failed to run the solution")?;
+        let running_time = start_time.elapsed();
+
+        let join_err = |_| eyre::eyre!("Failed to communicate with the solution");
+
+        input_writer
+            .join()
+            .map_err(join_err)?
+            .wrap_err("Failed to write test case input to your solution")?;
+
+        let stdout = stdout_reader
+            .join()
+            .map_err(join_err)?
+            .wrap_err("Failed to read the output of your solution")?;
+        let stderr = stderr_reader
+            .join()
+            .map_err(join_err)?
+            .wrap_err("Failed to read the output of your solution")?;
+
+        Ok(ExecutionOutput {
+            output: process::Output {
+                status,
+                stdout,
+                stderr,
+            },
+            running_time,
+            time_limit_exceeded,
+            peak_memory,
+        })
+    })
+}
+
+/// Runs the solution and the interactor at the same time, piping the output of
+/// each program into the other. Everything they send to each other is
+/// recorded in the transcript. Limits only apply to the solution.
+pub fn run_interactive(
+    app: &App,
+    run_cmd: &[String],
+    interactor_cmd: &[String],
+    limits: ResourceLimits,
+) -> crate::Result<InteractiveExecutionOutput> {
+    let mut solution = spawn_with_limits(app, run_cmd, limits)?;
+    let mut interactor = match spawn_with_limits(app, interactor_cmd, ResourceLimits::default()) {
+        Ok(interactor) => interactor,
+        Err(e) => {
+            // The solution would otherwise wait for input forever.
+            solution.kill().ok();
+            return Err(e);
+        }
+    };
+
+    let (solution_stdin, solution_stdout, mut solution_stderr) = take_pipes(&mut solution)?;
+    let (interactor_stdin, interactor_stdout, mut interactor_stderr) = take_pipes(&mut interactor)?;
+
+    let transcript = Mutex::new(Transcript::default());
+    let start_time = Instant::now();
+
+    thread::scope(|scope| {
+        let transcript = &transcript;
+
+        let solution_relay = scope.spawn(move || {
+            relay(
+                solution_stdout,
+                interactor_stdin,
+                Direction::FromSolution,
+                transcript,
+            )
+        });
+        let interactor_relay = scope.spawn(move || {
+            relay(
+                interactor_stdout,
+                solution_stdin,
+                Direction::ToSolution,
+                transcript,
+            )
+        });
+        let solution_stderr_reader = scope.spawn(move || read_all(&mut solution_stderr));
+        let interactor_stderr_reader = scope.spawn(move || read_all(&mut interactor_stderr));
+
+        // The threads above only finish once both programs have exited, so
+        // the programs must be stopped before returning early. Otherwise, the
+        // scope would wait for the threads forever.
+        let solution_exit = match wait_with_time_limit(&mut solution, limits.time) {
+            Ok(exit) => exit,
+            Err(e) => {
+                kill_and_reap(&mut solution);
+                kill_and_reap(&mut interactor);
+                return Err(e).wrap_err("Failed to run the solution");
+            }
+        };
+        let running_time = start_time.elapsed();
+
+        // Once the solution has exited, the interactor sees EOF and should exit
+        // as well. It is killed if it doesn't.
+        let interactor_exit =
+            match wait_with_time_limit(&mut interactor, Some(INTERACTOR_EXIT_GRACE_PERIOD)) {
+                Ok(exit) => exit,
+                Err(e) => {
+                    kill_and_reap(&mut interactor);
+                    return Err(e).wrap_err("Failed to run the interactor");
+                }
+            };
+
+        let join_err = |_| eyre::eyre!("Failed to communicate with the solution");
+
+        let solution_stdout = solution_relay
+            .join()
+            .map_err(join_err)?
+            .wrap_err("Failed to pass output from your solution to the interactor")?;
+        let interactor_stdout = interactor_relay
+            .join()
+            .map_err(join_err)?
+            .wrap_err("Failed to pass output from the interactor to your solution")?;
+
+        let solution_stderr = solution_stderr_reader
+            .join()
+            .map_err(join_err)?
+            .wrap_err("Failed to read the output of your solution")?;
+        let interactor_stderr = interactor_stderr_reader
+            .join()
+            .map_err(join_err)?
+            .wrap_err("Failed to read the output of the interactor")?;
+
+        eyre::ensure!(
+            !interactor_exit.time_limit_exceeded,
+            "The interactor did not exit within {}s after your solution finished",
+            INTERACTOR_EXIT_GRACE_PERIOD.as_secs()
+        );
+
+        Ok(InteractiveExecutionOutput {
+            solution: ExecutionOutput {
+                output: process::Output {
+                    status: solution_exit.status,
+                    stdout: solution_stdout,
+                    stderr: solution_stderr,
+                },
+                running_time,
+                time_limit_exceeded: solution_exit.time_limit_exceeded,
+                peak_memory: solution_exit.peak_memory,
+            },
+            interactor: process::Output {
+                status: interactor_exit.status,
+                stdout: interactor_stdout,
+                stderr: interactor_stderr,
+            },
+            transcript: transcript
+                .lock()
+                .map(|mut transcript| std::mem::take(&mut *transcript))
+                .unwrap_or_default(),
+        })
+    })
+}
+
+/// Copies everything from `from` into `to` while recording it in the
+/// transcript. Returns everything that was read. If the receiving program
+/// exits, the rest is still read such that the sending program never blocks.
+fn relay(
+    mut from: impl Read,
+    to: impl Write,
+    direction: Direction,
+    transcript: &Mutex<Transcript>,
+) -> io::Result<Vec<u8>> {
+    let mut to = Some(to);
+    let mut everything_read = Vec::new();
+    let mut buf = [0; 8192];
+
+    loop {
+        let num_bytes = match from.read(&mut buf) {
+            Ok(0) => break,
+            Ok(num_bytes) => num_bytes,
+            Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+            Err(e) => return Err(e),
+        };
+
+        let bytes = &buf[..num_bytes];
+        everything_read.extend_from_slice(bytes);
+
+        if let Ok(mut transcript) = transcript.lock() {
+            transcript.record(direction, bytes);
+        }
+
+        if let Some(writer) = &mut to {
+            if writer
+                .write_all(bytes)
+                .and_then(|_| writer.flush())
+                .is_err()
+            {
+                to = None;
+            }
+        }
+    }
+
+    // Dropping the writer closes the pipe, which sends EOF to the receiver.
+    drop(to);
+
+    Ok(everything_read)
+}
+
+fn spawn_with_limits(
+    app: &App,
+    run_cmd: &[String],
+    limits: ResourceLimits,
+) -> crate::Result<Child> {
     let (run_program, run_program_args) = run_cmd
         .split_first()
         .ok_or_else(|| eyre::eyre!("Run command is empty"))?;
@@ -87,36 +555,178 @@ pub fn run_with_input(
         );
     }
 
-    let mut child = Command::new(run_program)
+    let mut command = Command::new(run_program);
+    command
         .args(run_program_args)
         .stdin(Stdio::piped())
         .stdout(Stdio::piped())
-        .stderr(Stdio::piped())
-        .spawn()
-        .map_err(|err| match err.kind() {
-            io::ErrorKind::NotFound => {
-                eyre::eyre!("Failed to find the runner program '{}'", run_program)
-            }
-            _ => eyre::eyre!("Failed to run the runner program: {}", err),
-        })?;
+        .stderr(Stdio::piped());
+
+    #[cfg(target_os = "linux")]
+    if let Some(memory_limit) = limits.memory {
+        limit_address_space(
+            &mut command,
+            memory_limit.saturating_mul(ADDRESS_SPACE_HEADROOM_FACTOR),
+        );
+    }
 
-    let mut child_stdin = child
+    #[cfg(not(target_os = "linux"))]
+    let _ = limits;
+
+    command.spawn().map_err(|err| match err.kind() {
+        io::ErrorKind::NotFound => {
+            eyre::eyre!("Failed to find the runner program '{}'", run_program)
+        }
+        _ => eyre::eyre!("Failed to run the runner program: {}", err),
+    })
+}
+
+fn take_pipes(child: &mut Child) -> crate::Result<(ChildStdin, ChildStdout, ChildStderr)> {
+    let stdin = child
         .stdin
         .take()
         .ok_or_else(|| eyre::eyre!("Failed to capture stdin of your solution"))?;
+    let stdout = child
+        .stdout
+        .take()
+        .ok_or_else(|| eyre::eyre!("Failed to capture stdout of your solution"))?;
+    let stderr = child
+        .stderr
+        .take()
+        .ok_or_else(|| eyre::eyre!("Failed to capture stderr of your solution"))?;
+
+    Ok((stdin, stdout, stderr))
+}
+
+fn read_all(reader: &mut impl Read) -> io::Result<Vec<u8>> {
+    let mut buf = Vec::new();
+    reader.read_to_end(&mut buf)?;
+    Ok(buf)
+}
+
+struct ChildExit {
+    status: ExitStatus,
+    time_limit_exceeded: bool,
+    peak_memory: Option<u64>,
+}
+
+/// Waits for the child to exit. If it is still running once the time limit has
+/// passed, it is killed.
+/// Stops a program that has not been waited for yet. Errors are ignored, since
+/// the program may have exited already.
+fn kill_and_reap(child: &mut Child) {
+    child.kill().ok();
+    child.wait().ok();
+}
+
+fn wait_with_time_limit(child: &mut Child, time_limit: Option<Duration>) -> io::Result<ChildExit> {
+    let exit = |(status, peak_memory), time_limit_exceeded| ChildExit {
+        status,
+        time_limit_exceeded,
+        peak_memory,
+    };
+
+    let Some(time_limit) = time_limit else {
+        return Ok(exit(wait_with_peak_memory(child)?, false));
+    };
 
-    io::copy(input, &mut child_stdin)
-        .wrap_err("Failed to write test case input to your solution")?;
+    let start_time = Instant::now();
+
+    loop {
+        if let Some(exited) = try_wait_with_peak_memory(child)? {
+            return Ok(exit(exited, false));
+        }
+
+        let elapsed = start_time.elapsed();
+        if elapsed >= time_limit {
+            child.kill()?;
+            return Ok(exit(wait_with_peak_memory(child)?, true));
+        }
+
+        thread::sleep(TIME_LIMIT_POLL_INTERVAL.min(time_limit - elapsed));
+    }
+}
 
-    // Manually drop stdin to ensure that EOF is sent. If this is not done, the
-    // child process might not terminate if it reads until EOF.
-    drop(child_stdin);
+fn wait_with_peak_memory(child: &mut Child) -> io::Result<(ExitStatus, Option<u64>)> {
+    #[cfg(unix)]
+    return wait4(child, 0).map(|exited| exited.expect("wait4 blocks until the child exits"));
 
-    let output = child
-        .wait_with_output()
-        .wrap_err("Failed to run the solution")?;
+    #[cfg(not(unix))]
+    return child.wait().map(|status| (status, None));
+}
+
+fn try_wait_with_peak_memory(child: &mut Child) -> io::Result<Option<(ExitStatus, Option<u64>)>> {
+    #[cfg(unix)]
+    return wait4(child, libc::WNOHANG);
 
-    Ok(output)
+    #[cfg(not(unix))]
+    return child
+        .try_wait()
+        .map(|status| status.map(|status| (status, None)));
+}
+
+/// Reaps the child with `wait4(2)` rather than through [`Child::wait`], since
+/// that is the only way to get the resource usage of that specific child.
+#[cfg(unix)]
+fn wait4(child: &Child, options: libc::c_int) -> io::Result<Option<(ExitStatus, Option<u64>)>> {
+    use std::os::unix::process::ExitStatusExt;
+
+    let pid = child.id() as libc::pid_t;
+    let mut status = 0;
+
+    loop {
+        // SAFETY: `rusage` is a plain C struct for which all zeroes is valid.
+        let mut usage: libc::rusage = unsafe { std::mem::zeroed() };
+
+        // SAFETY: `status` and `usage` are valid pointers for the duration of
+        // the call.
+        let res = unsafe { libc::wait4(pid, &mut status, options, &mut usage) };
+
+        match res {
+            0 => return Ok(None),
+            -1 => {
+                let err = io::Error::last_os_error();
+
+                if err.kind() != io::ErrorKind::Interrupted {
+                    return Err(err);
+                }
+            }
+            _ => {
+                // Linux reports the maximum resident set size in kilobytes,
+                // whereas macOS reports it in bytes.
+                let max_rss = u64::try_from(usage.ru_maxrss).unwrap_or_default();
+                let peak_memory = if cfg!(target_os = "macos") {
+                    max_rss
+                } else {
+                    max_rss * 1024
+                };
+
+                return Ok(Some((ExitStatus::from_raw(status), Some(peak_memory))));
+            }
+        }
+    }
+}
+
+#[cfg(target_os = "linux")]
+fn limit_address_space(command: &mut Command, max_bytes: u64) {
+    use std::os::unix::process::CommandExt;
+
+    let limit = libc::rlimit {
+        rlim_cur: max_bytes as libc::rlim_t,
+        rlim_max: max_bytes as libc::rlim_t,
+    };
+
+    // SAFETY: `setrlimit` is async-signal-safe, so it may be called between
+    // fork and exec.
+    unsafe {
+        command.pre_exec(move || {
+            if libc::setrlimit(libc::RLIMIT_AS, &limit) != 0 {
+                return Err(io::Error::last_os_error());
+            }
+
+            Ok(())
+        });
+    }
 }
 
 pub fn run_compile_cmd(app: &App, compile_cmd: &[String]) -> crate::Result<()> {
@@ -155,20 +765,10 @@ pub fn run_compile_cmd(app: &App, compile_cmd: &[String]) -> crate::Result<()> {
     Ok(())
 }
 
-pub fn are_equal_after_normalisation(a: &str, b: &str) -> bool {
-    fn normalise(s: &str) -> String {
-        s.trim_end()
-            .lines()
-            .map(str::trim_end)
-            .collect::<Vec<_>>()
-            .join("\n")
-    }
-
-    normalise(a) == normalise(b)
-}
-
 pub struct FileTestCase {
     pub name: String,
+    /// The subfolder of the test folder that the test case is in, if any.
+    pub group: Option<String>,
     pub input_file: PathBuf,
     pub answer_file: PathBuf,
 }
@@ -189,6 +789,9 @@ impl TestCaseIO for FileTestCase {
 #[derive(Debug)]
 pub struct TestCaseInfo {
     pub running_time: Duration,
+    pub peak_memory: Option<u64>,
+    pub stdout: String,
+    pub stderr: String,
 }
 
 #[derive(Debug)]
@@ -197,12 +800,41 @@ pub enum TestCaseError {
         input: String,
         expected: String,
         actual: String,
+        /// Where the output was saved with --save-output, if it was.
+        saved_output: Option<PathBuf>,
         stderr: String,
+        /// Feedback written by the output validator, if any.
+        feedback: Option<String>,
+        running_time: Duration,
     },
     RuntimeError {
         input: String,
         stdout: String,
         stderr: String,
+        running_time: Duration,
+    },
+    TimeLimitExceeded {
+        input: String,
+        elapsed: Duration,
+        time_limit: Duration,
+        stdout: String,
+        stderr: String,
+    },
+    MemoryLimitExceeded {
+        input: String,
+        peak_memory: Option<u64>,
+        memory_limit: u64,
+        stdout: String,
+        stderr: String,
+        running_time: Duration,
+    },
+    WrongInteraction {
+        input: String,
+        transcript: String,
+        stderr: String,
+        /// Feedback written by the interactor, if any.
+        feedback: Option<String>,
+        running_time: Duration,
     },
 }
 
@@ -211,32 +843,225 @@ impl TestCaseError {
         match self {
             TestCaseError::RuntimeError { ref input, .. } => input,
             TestCaseError::WrongAnswer { ref input, .. } => input,
+            TestCaseError::TimeLimitExceeded { ref input, .. } => input,
+            TestCaseError::MemoryLimitExceeded { ref input, .. } => input,
+            TestCaseError::WrongInteraction { ref input, .. } => input,
         }
     }
 
-    pub fn print(&self, app: &App) {
+    pub fn running_time(&self) -> Duration {
         match self {
+            TestCaseError::TimeLimitExceeded { elapsed, .. } => *elapsed,
+            TestCaseError::WrongAnswer { running_time, .. }
+            | TestCaseError::RuntimeError { running_time, .. }
+            | TestCaseError::MemoryLimitExceeded { running_time, .. }
+            | TestCaseError::WrongInteraction { running_time, .. } => *running_time,
+        }
+    }
+
+    /// Prints the explanation of the failure. Outputs that are too large to be
+    /// shown are saved to a temporary file first, unless they have been saved
+    /// already, such that the full output can be looked at.
+    pub fn print(&self, app: &App) -> crate::Result<()> {
+        let full_output_path = match self {
+            TestCaseError::WrongAnswer {
+                expected,
+                actual,
+                saved_output: None,
+                ..
+            } if diff::is_large_output(expected, actual) => Some(save_full_output(actual)?),
+            _ => None,
+        };
+
+        let mut details = self.details(app.args.should_show_wrong_answer_stderr());
+        details.full_output_path = full_output_path.as_deref();
+
+        print!("{details}");
+
+        Ok(())
+    }
+
+    /// The explanation of the failure that is shown below a failed test.
+    pub fn details(&self, show_stderr: bool) -> TestCaseErrorDetails<'_> {
+        TestCaseErrorDetails {
+            error: self,
+            show_stderr,
+            full_output_path: None,
+        }
+    }
+}
+
+pub struct TestCaseErrorDetails<'a> {
+    error: &'a TestCaseError,
+    show_stderr: bool,
+    /// Where the full output of a wrong answer was saved, if it was not saved
+    /// with the other outputs of the test case.
+    full_output_path: Option<&'a Path>,
+}
+
+impl fmt::Display for TestCaseErrorDetails<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.error {
             TestCaseError::WrongAnswer {
                 expected,
                 actual,
+                saved_output,
                 stderr,
+                feedback,
                 ..
             } => {
-                println!("{}", "Expected:".underline());
-                println!("{}\n", expected.trim_end());
-                println!("{}", "Actual:".underline());
-                println!("{}\n", actual.trim_end());
-
-                if app.args.should_show_wrong_answer_stderr() {
-                    println!("{}", "Stderr:".underline());
-                    println!("{}\n", stderr.trim_end());
+                if diff::is_large_output(expected, actual) {
+                    writeln!(f, "{}", Diff::new(expected, actual))?;
+
+                    if let Some(path) = saved_output.as_deref().or(self.full_output_path) {
+                        writeln!(
+                            f,
+                            "The output is too large to show in full. Your full output was saved to {}\n",
+                            path.display().to_string().underline()
+                        )?;
+                    }
+                } else {
+                    writeln!(f, "{}", "Expected:".underline())?;
+                    writeln!(f, "{}\n", expected.trim_end())?;
+                    writeln!(f, "{}", "Actual:".underline())?;
+                    writeln!(f, "{}\n", actual.trim_end())?;
+                }
+
+                if let Some(feedback) = feedback {
+                    writeln!(f, "{}", "Validator feedback:".underline())?;
+                    writeln!(f, "{}\n", feedback.trim_end())?;
+                }
+
+                if self.show_stderr {
+                    writeln!(f, "{}", "Stderr:".underline())?;
+                    writeln!(f, "{}\n", stderr.trim_end())?;
                 }
             }
             TestCaseError::RuntimeError { stdout, stderr, .. } => {
-                println!("{}:", "Runtime error".bright_red());
-                println!("{}", stdout.trim_end());
-                println!("{}\n", stderr.trim_end());
+                writeln!(f, "{}:", "Runtime error".bright_red())?;
+                writeln!(f, "{}", stdout.trim_end())?;
+                writeln!(f, "{}\n", stderr.trim_end())?;
+            }
+            TestCaseError::TimeLimitExceeded {
+                elapsed,
+                time_limit,
+                stderr,
+                ..
+            } => {
+                writeln!(
+                    f,
+                    "{}: killed after {:.2}s (limit is {:.2}s)",
+                    "Time limit exceeded".bright_red(),
+                    elapsed.as_secs_f64(),
+                    time_limit.as_secs_f64()
+                )?;
+
+                if self.show_stderr {
+                    writeln!(f, "{}", "Stderr:".underline())?;
+                    writeln!(f, "{}", stderr.trim_end())?;
+                }
+
+                writeln!(f)?;
+            }
+            TestCaseError::MemoryLimitExceeded {
+                peak_memory,
+                memory_limit,
+                stderr,
+                ..
+            } => {
+                let usage = match peak_memory {
+                    Some(peak_memory) if peak_memory > memory_limit => {
+                        format!("used {}", format_memory(*peak_memory))
+                    }
+                    _ => "failed to allocate memory".to_string(),
+                };
+
+                writeln!(
+                    f,
+                    "{}: {usage} (limit is {})",
+                    "Memory limit exceeded".bright_red(),
+                    format_memory(*memory_limit)
+                )?;
+
+                if self.show_stderr {
+                    writeln!(f, "{}", "Stderr:".underline())?;
+                    writeln!(f, "{}", stderr.trim_end())?;
+                }
+
+                writeln!(f)?;
+            }
+            TestCaseError::WrongInteraction {
+                transcript,
+                stderr,
+                feedback,
+                ..
+            } => {
+                writeln!(
+                    f,
+                    "{} (< is sent to your solution, > is sent by it):",
+                    "Transcript".underline()
+                )?;
+                writeln!(f, "{}\n", transcript.trim_end())?;
+
+                if let Some(feedback) = feedback {
+                    writeln!(f, "{}", "Interactor feedback:".underline())?;
+                    writeln!(f, "{}\n", feedback.trim_end())?;
+                }
+
+                if self.show_stderr {
+                    writeln!(f, "{}", "Stderr:".underline())?;
+                    writeln!(f, "{}\n", stderr.trim_end())?;
+                }
             }
         }
+
+        Ok(())
     }
 }
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Direction {
+    ToSolution,
+    FromSolution,
+}
+
+/// Everything the solution and the interactor sent to each other, in the
+/// order it was sent.
+#[derive(Debug, Default)]
+pub struct Transcript {
+    messages: Vec<(Direction, Vec<u8>)>,
+}
+
+impl Transcript {
+    fn record(&mut self, direction: Direction, bytes: &[u8]) {
+        match self.messages.last_mut() {
+            Some((last_direction, message)) if *last_direction == direction => {
+                message.extend_from_slice(bytes)
+            }
+            _ => self.messages.push((direction, bytes.to_vec())),
+        }
+    }
+}
+
+impl fmt::Display for Transcript {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (direction, message) in &self.messages {
+            let prefix = match direction {
+                Direction::ToSolution => "<",
+                Direction::FromSolution => ">",
+            };
+
+            for line in String::from_utf8_lossy(message).lines() {
+                writeln!(f, "{prefix} {line}")?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Formats a number of bytes as megabytes (more precisely mebibytes, which is
+/// what Kattis uses).
+pub fn format_memory(bytes: u64) -> String {
+    format!("{:.1} MB", bytes as f64 / BYTES_PER_MEGABYTE as f64)
+}
@@ -0,0 +1,152 @@
+use std::{
+    fs,
+    io::Write,
+    path::Path,
+    process::{self, ExitStatus},
+};
+
+use colored::Colorize;
+use eyre::Context;
+
+use crate::{test_io::run_with_input, App};
+
+use super::Judgement;
+
+/// Exit code used by output validators to accept an output.
+const EXIT_CODE_ACCEPTED: i32 = 42;
+/// Exit code used by output validators to reject an output.
+const EXIT_CODE_REJECTED: i32 = 43;
+
+/// Feedback files that validators may write to their feedback directory.
+const FEEDBACK_FILES: [&str; 2] = ["judgemessage.txt", "teammessage.txt"];
+
+/// An output validator program following Kattis' calling convention: it is run
+/// as `validator input_file answer_file feedback_dir < output`, and it must
+/// exit with code 42 to accept the output and 43 to reject it. Interactors use
+/// the same convention, except that they talk to the solution while it runs.
+#[derive(Debug)]
+pub struct CustomValidator {
+    pub run_cmd: Vec<String>,
+}
+
+impl CustomValidator {
+    pub fn judge(
+        &self,
+        app: &App,
+        input: &str,
+        expected: &str,
+        actual: &str,
+    ) -> crate::Result<Judgement> {
+        let run = self.prepare(input, expected)?;
+        let output = run_with_input(app, run.cmd(), &mut actual.as_bytes())?;
+
+        run.judgement(output.status)?
+            .ok_or_else(|| run.unexpected_exit_error("output validator", &output))
+    }
+
+    /// Saves the input and answer to temporary files such that the validator
+    /// can be run. The files are removed when the returned run is dropped.
+    pub fn prepare(&self, input: &str, expected: &str) -> crate::Result<ValidatorRun> {
+        let input_file = write_temp_file(input).wrap_err("Failed to save input for validator")?;
+        let answer_file =
+            write_temp_file(expected).wrap_err("Failed to save answer for validator")?;
+        let feedback_dir =
+            tempfile::tempdir().wrap_err("Failed to create feedback folder for validator")?;
+
+        let mut cmd = self.run_cmd.clone();
+        for path in [input_file.path(), answer_file.path(), feedback_dir.path()] {
+            cmd.push(path_to_string(path)?);
+        }
+
+        Ok(ValidatorRun {
+            cmd,
+            _input_file: input_file,
+            _answer_file: answer_file,
+            feedback_dir,
+        })
+    }
+}
+
+/// A single run of a validator along with the files it is given.
+#[derive(Debug)]
+pub struct ValidatorRun {
+    cmd: Vec<String>,
+    _input_file: tempfile::NamedTempFile,
+    _answer_file: tempfile::NamedTempFile,
+    feedback_dir: tempfile::TempDir,
+}
+
+impl ValidatorRun {
+    /// The command to run the validator with, including its arguments.
+    pub fn cmd(&self) -> &[String] {
+        &self.cmd
+    }
+
+    /// Interprets the exit status of the validator. Returns `None` if it exited
+    /// with an exit code that is neither accept nor reject.
+    pub fn judgement(&self, status: ExitStatus) -> crate::Result<Option<Judgement>> {
+        match status.code() {
+            Some(EXIT_CODE_ACCEPTED) => Ok(Some(Judgement::Accepted)),
+            Some(EXIT_CODE_REJECTED) => Ok(Some(Judgement::Rejected {
+                feedback: read_feedback(self.feedback_dir.path())?,
+            })),
+            _ => Ok(None),
+        }
+    }
+
+    pub fn unexpected_exit_error(&self, kind: &str, output: &process::Output) -> eyre::Report {
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let stderr = String::from_utf8_lossy(&output.stderr);
+
+        eyre::eyre!(
+            indoc::indoc! {"
+                Your {kind} exited with an unexpected exit code ({status}). It must exit with {accepted} to accept and {rejected} to reject.
+
+                {label}:
+                {stdout}
+                {stderr}\
+            "},
+            kind = kind,
+            status = output.status,
+            accepted = EXIT_CODE_ACCEPTED,
+            rejected = EXIT_CODE_REJECTED,
+            label = format!("Output of your {kind}").bright_red(),
+            stdout = stdout.trim_end(),
+            stderr = stderr.trim_end(),
+        )
+    }
+}
+
+fn write_temp_file(contents: &str) -> crate::Result<tempfile::NamedTempFile> {
+    let mut file = tempfile::NamedTempFile::new()?;
+    file.write_all(contents.as_bytes())?;
+    Ok(file)
+}
+
+fn path_to_string(path: &Path) -> crate::Result<String> {
+    path.to_str()
+        .map(str::to_string)
+        .ok_or_else(|| eyre::eyre!("Could not convert path to string"))
+}
+
+/// Collects the contents of the feedback files the validator wrote, if any.
+fn read_feedback(feedback_dir: &Path) -> crate::Result<Option<String>> {
+    let mut feedback = Vec::new();
+
+    for file_name in FEEDBACK_FILES {
+        let path = feedback_dir.join(file_name);
+
+        if !path.is_file() {
+            continue;
+        }
+
+        let contents = fs::read_to_string(&path)
+            .wrap_err_with(|| format!("Failed to read validator feedback file {file_name}"))?;
+
+        if !contents.trim().is_empty() {
+            feedback.push(contents.trim_end().to_string());
+        }
+    }
+
+    Ok((!feedback.is_empty()).then(|| feedback.join("\n")))
+}
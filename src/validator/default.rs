@@ -0,0 +1,139 @@
+/// Flags for the default output validator. The flags mirror those of Kattis'
+/// default output validator and are written in the same format, for example
+/// `case_sensitive float_tolerance 1e-6`.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct DefaultValidatorFlags {
+    pub case_sensitive: bool,
+    pub space_change_sensitive: bool,
+    pub float_absolute_tolerance: Option<f64>,
+    pub float_relative_tolerance: Option<f64>,
+}
+
+impl DefaultValidatorFlags {
+    pub fn parse(flags: &str) -> crate::Result<Self> {
+        let mut parsed = Self::default();
+        let mut tokens = flags.split_whitespace();
+
+        while let Some(flag) = tokens.next() {
+            let mut tolerance = || -> crate::Result<f64> {
+                let value = tokens
+                    .next()
+                    .ok_or_else(|| eyre::eyre!("Validator flag '{flag}' must be given a value"))?;
+
+                Self::parse_tolerance(value).ok_or_else(|| {
+                    eyre::eyre!("Validator flag '{flag}' was given an invalid value '{value}'")
+                })
+            };
+
+            match flag {
+                "case_sensitive" => parsed.case_sensitive = true,
+                "space_change_sensitive" => parsed.space_change_sensitive = true,
+                "float_absolute_tolerance" => {
+                    parsed.float_absolute_tolerance = Some(tolerance()?);
+                }
+                "float_relative_tolerance" => {
+                    parsed.float_relative_tolerance = Some(tolerance()?);
+                }
+                "float_tolerance" => parsed.set_float_tolerance(tolerance()?),
+                _ => eyre::bail!("Unknown validator flag '{flag}'"),
+            }
+        }
+
+        Ok(parsed)
+    }
+
+    /// Parses a float tolerance, which must be a finite, non-negative number.
+    pub fn parse_tolerance(value: &str) -> Option<f64> {
+        value
+            .parse::<f64>()
+            .ok()
+            .filter(|tolerance| tolerance.is_finite() && *tolerance >= 0.0)
+    }
+
+    /// Sets both the absolute and the relative tolerance, like the
+    /// `float_tolerance` flag does.
+    pub fn set_float_tolerance(&mut self, tolerance: f64) {
+        self.float_absolute_tolerance = Some(tolerance);
+        self.float_relative_tolerance = Some(tolerance);
+    }
+
+    fn has_float_tolerance(&self) -> bool {
+        self.float_absolute_tolerance.is_some() || self.float_relative_tolerance.is_some()
+    }
+}
+
+/// Compares the output token by token with the same semantics as Kattis'
+/// default output validator. Unless the output is space change sensitive, any
+/// amount of whitespace between tokens is accepted. Trailing whitespace at the
+/// end of the output is always ignored.
+pub fn is_accepted(flags: &DefaultValidatorFlags, expected: &str, actual: &str) -> bool {
+    let mut expected_tokens = tokenise(expected.trim_end(), flags.space_change_sensitive);
+    let mut actual_tokens = tokenise(actual.trim_end(), flags.space_change_sensitive);
+
+    loop {
+        match (expected_tokens.next(), actual_tokens.next()) {
+            (None, None) => return true,
+            (Some(expected), Some(actual)) if tokens_match(flags, expected, actual) => {}
+            _ => return false,
+        }
+    }
+}
+
+fn tokens_match(flags: &DefaultValidatorFlags, expected: &str, actual: &str) -> bool {
+    if flags.has_float_tolerance() {
+        if let Ok(expected) = expected.parse::<f64>() {
+            return actual
+                .parse::<f64>()
+                .is_ok_and(|actual| floats_match(flags, expected, actual));
+        }
+    }
+
+    if flags.case_sensitive {
+        expected == actual
+    } else {
+        expected.eq_ignore_ascii_case(actual)
+    }
+}
+
+fn floats_match(flags: &DefaultValidatorFlags, expected: f64, actual: f64) -> bool {
+    if expected.is_nan() || actual.is_nan() {
+        return false;
+    }
+
+    if expected == actual {
+        return true;
+    }
+
+    let difference = (expected - actual).abs();
+
+    let within_absolute = flags
+        .float_absolute_tolerance
+        .is_some_and(|tolerance| difference <= tolerance);
+    let within_relative = flags
+        .float_relative_tolerance
+        .is_some_and(|tolerance| difference <= tolerance * expected.abs());
+
+    within_absolute || within_relative
+}
+
+/// Splits the text into tokens. If whitespace is significant, each run of
+/// whitespace is a token of its own.
+fn tokenise(text: &str, keep_whitespace: bool) -> impl Iterator<Item = &str> {
+    let mut rest = text;
+
+    std::iter::from_fn(move || {
+        if !keep_whitespace {
+            rest = rest.trim_start();
+        }
+
+        let first = rest.chars().next()?;
+        let end = rest
+            .find(|c: char| c.is_whitespace() != first.is_whitespace())
+            .unwrap_or(rest.len());
+
+        let (token, remaining) = rest.split_at(end);
+        rest = remaining;
+
+        Some(token)
+    })
+}
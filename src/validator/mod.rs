@@ -0,0 +1,191 @@
+use std::path::{Path, PathBuf};
+
+use color_eyre::owo_colors::OwoColorize;
+use eyre::Context;
+
+use crate::{
+    problem::{read_interactor_path, read_validator_flags, read_validator_path},
+    solution::get_all_files_with_known_extension,
+    test_io::run_compile_cmd,
+    App,
+};
+
+pub use self::{custom::CustomValidator, default::DefaultValidatorFlags};
+
+mod custom;
+mod default;
+
+/// Decides whether the output of a solution is correct.
+#[derive(Debug)]
+pub enum OutputValidator {
+    /// Compares the output with the expected answer like Kattis' default output
+    /// validator.
+    Default(DefaultValidatorFlags),
+    /// Runs a validator program written by the user.
+    Custom(CustomValidator),
+}
+
+#[derive(Debug)]
+pub enum Judgement {
+    Accepted,
+    Rejected { feedback: Option<String> },
+}
+
+#[derive(Debug, Default)]
+pub struct OutputValidatorOptions<'a> {
+    /// Path to a validator program to use instead of the default validator.
+    pub validator_path: Option<&'a Path>,
+    /// Overrides the validator flags of the problem.
+    pub validator_flags: Option<&'a str>,
+    /// Overrides the float tolerance given by the validator flags.
+    pub float_tolerance: Option<f64>,
+}
+
+impl OutputValidator {
+    /// Resolves the validator to use for the solution in the given folder.
+    ///
+    /// A validator program is used if one is given in the options, in the
+    /// problem file, or if the solution folder has a 'validator' folder.
+    /// Otherwise, the default validator is used with the validator flags from
+    /// the problem file unless they are overridden by the options.
+    pub fn for_solution_dir(
+        app: &App,
+        solution_dir: impl AsRef<Path>,
+        options: OutputValidatorOptions,
+    ) -> crate::Result<Self> {
+        let solution_dir = solution_dir.as_ref();
+
+        let configured_path = match options.validator_path {
+            Some(path) => Some(path.to_path_buf()),
+            None => read_validator_path(solution_dir)?,
+        };
+
+        let validator_file = resolve_program_file(
+            app,
+            configured_path,
+            &get_validator_dir(solution_dir),
+            "output validator",
+        )?;
+
+        if let Some(validator_file) = validator_file {
+            let validator = build_custom_validator(app, &validator_file, "output validator")?;
+            return Ok(Self::Custom(validator));
+        }
+
+        let flags = match options.validator_flags {
+            Some(flags) => Some(flags.to_string()),
+            None => read_validator_flags(solution_dir)?,
+        };
+
+        let mut flags = flags
+            .as_deref()
+            .map(DefaultValidatorFlags::parse)
+            .transpose()?
+            .unwrap_or_default();
+
+        if let Some(tolerance) = options.float_tolerance {
+            flags.set_float_tolerance(tolerance);
+        }
+
+        Ok(Self::Default(flags))
+    }
+
+    pub fn judge(
+        &self,
+        app: &App,
+        input: &str,
+        expected: &str,
+        actual: &str,
+    ) -> crate::Result<Judgement> {
+        match self {
+            OutputValidator::Default(flags) => {
+                if default::is_accepted(flags, expected, actual) {
+                    Ok(Judgement::Accepted)
+                } else {
+                    Ok(Judgement::Rejected { feedback: None })
+                }
+            }
+            OutputValidator::Custom(validator) => validator.judge(app, input, expected, actual),
+        }
+    }
+}
+
+/// Resolves the interactor to use for the solution in the given folder, if the
+/// problem is interactive. An interactor is used if a path is given, if the
+/// problem file has an `interactor` field, or if the solution folder has an
+/// 'interactor' folder.
+pub fn resolve_interactor(
+    app: &App,
+    solution_dir: impl AsRef<Path>,
+    interactor_path: Option<&Path>,
+) -> crate::Result<Option<CustomValidator>> {
+    let solution_dir = solution_dir.as_ref();
+
+    let configured_path = match interactor_path {
+        Some(path) => Some(path.to_path_buf()),
+        None => read_interactor_path(solution_dir)?,
+    };
+
+    resolve_program_file(
+        app,
+        configured_path,
+        &get_interactor_dir(solution_dir),
+        "interactor",
+    )?
+    .map(|file| build_custom_validator(app, &file, "interactor"))
+    .transpose()
+}
+
+pub fn get_validator_dir(solution_dir: impl AsRef<Path>) -> PathBuf {
+    solution_dir.as_ref().join("validator")
+}
+
+pub fn get_interactor_dir(solution_dir: impl AsRef<Path>) -> PathBuf {
+    solution_dir.as_ref().join("interactor")
+}
+
+fn build_custom_validator(app: &App, file: &Path, kind: &str) -> crate::Result<CustomValidator> {
+    let lang = app.config.try_lang_from_file(file)?;
+    let exec_cmds = lang.get_program_execution_commands(file)?;
+
+    if let Some(compile_cmd) = exec_cmds.compile_cmd() {
+        run_compile_cmd(app, compile_cmd)
+            .wrap_err_with(|| format!("Failed to compile your {kind}"))?;
+    }
+
+    Ok(CustomValidator {
+        run_cmd: exec_cmds.run_cmd().to_vec(),
+    })
+}
+
+/// Finds the program to use, either from the configured path or as the only
+/// source file in the given folder.
+fn resolve_program_file(
+    app: &App,
+    configured_path: Option<PathBuf>,
+    program_dir: &Path,
+    kind: &str,
+) -> crate::Result<Option<PathBuf>> {
+    if let Some(path) = configured_path {
+        eyre::ensure!(
+            path.is_file(),
+            "The {kind} path does not point to a file: '{}'",
+            path.display().underline()
+        );
+
+        return Ok(Some(path));
+    }
+
+    if !program_dir.is_dir() {
+        return Ok(None);
+    }
+
+    match get_all_files_with_known_extension(app, program_dir)?.as_slice() {
+        [] => eyre::bail!(
+            "No {kind} found in the folder: {}",
+            program_dir.display().underline()
+        ),
+        [file] => Ok(Some(file.clone())),
+        _ => eyre::bail!("Multiple {kind}s found in the folder. Specify which file to use."),
+    }
+}
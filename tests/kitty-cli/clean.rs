@@ -0,0 +1,89 @@
+use futures_util::FutureExt;
+
+use crate::helpers::{
+    contains, equals, make_standard_setup, run_with_sandbox, OutputSource::StdOut,
+};
+
+#[test]
+fn dry_run_lists_build_files_without_removing_them() {
+    run_with_sandbox(Box::new(|env| {
+        async move {
+            make_standard_setup(&env).await;
+
+            env.copy("./tests/kitty-cli/data/quadrant", "/work/quadrant");
+            env.copy("./tests/kitty-cli/data/quadrant.c", "/work/quadrant");
+
+            env.run("cd quadrant && kitty test -f quadrant.c").await;
+
+            env.run("cd quadrant && kitty clean --dry-run")
+                .await
+                .assert(StdOut, contains("Would remove ./.kitty/build/quadrant"));
+
+            env.run("ls quadrant/.kitty/build")
+                .await
+                .assert(StdOut, equals("quadrant"));
+        }
+        .boxed()
+    }));
+}
+
+#[test]
+fn recursive_clean_removes_build_files_of_all_solutions() {
+    run_with_sandbox(Box::new(|env| {
+        async move {
+            make_standard_setup(&env).await;
+
+            env.run("mkdir problems").await;
+            env.copy("./tests/kitty-cli/data/quadrant", "/work/problems/quadrant");
+            env.copy(
+                "./tests/kitty-cli/data/quadrant.c",
+                "/work/problems/quadrant",
+            );
+            env.copy("./tests/kitty-cli/data/quadrant", "/work/problems/other");
+            env.copy("./tests/kitty-cli/data/quadrant.c", "/work/problems/other");
+
+            env.run("cd problems/quadrant && kitty test -f quadrant.c")
+                .await;
+            env.run("cd problems/other && kitty test -f quadrant.c")
+                .await;
+
+            env.run("kitty clean problems --recursive").await.assert(
+                StdOut,
+                contains("2 build file(s) removed from 2 solution folder(s)"),
+            );
+
+            env.run("ls -A problems/quadrant")
+                .await
+                .assert(StdOut, equals("quadrant.c\nquadrant.py\ntest"));
+        }
+        .boxed()
+    }));
+}
+
+#[test]
+fn wildcard_artifacts_never_remove_tests_or_problem_files() {
+    run_with_sandbox(Box::new(|env| {
+        async move {
+            make_standard_setup(&env).await;
+
+            env.run(r"sed -i 's|^  run_command: python \$SRC_PATH$|&\n  artifacts: [$DIR_PATH/*]|' /root/.config/kitty/kitty.yml")
+                .await;
+            env.copy("./tests/kitty-cli/data/quadrant", "/work/quadrant");
+            env.run("cd quadrant && touch problem.md problem.yml output.txt && mkdir debug")
+                .await;
+
+            env.run("cd quadrant && kitty clean")
+                .await
+                .assert(StdOut, contains("Removed ./output.txt"));
+
+            env.run("ls -A quadrant").await.assert(
+                StdOut,
+                equals("debug\nproblem.md\nproblem.yml\nquadrant.py\ntest"),
+            );
+            env.run("ls quadrant/test")
+                .await
+                .assert(StdOut, equals("1.ans\n1.in\n2.ans\n2.in"));
+        }
+        .boxed()
+    }));
+}
@@ -0,0 +1,13 @@
+low, high = 1, 1000
+
+while True:
+    guess = (low + high) // 2
+    print(guess, flush=True)
+
+    response = input()
+    if response == "correct":
+        break
+    elif response == "higher":
+        low = guess + 1
+    else:
+        high = guess - 1
@@ -0,0 +1,30 @@
+import os
+import sys
+
+MAX_GUESSES = 10
+
+secret = int(open(sys.argv[1]).read())
+feedback_dir = sys.argv[3]
+
+
+def reject(message):
+    with open(os.path.join(feedback_dir, "judgemessage.txt"), "w") as f:
+        f.write(message)
+    sys.exit(43)
+
+
+for _ in range(MAX_GUESSES):
+    line = sys.stdin.readline()
+    if not line:
+        reject("solution stopped guessing")
+
+    guess = int(line)
+    if guess < secret:
+        print("higher", flush=True)
+    elif guess > secret:
+        print("lower", flush=True)
+    else:
+        print("correct", flush=True)
+        sys.exit(42)
+
+reject(f"too many guesses, the number was {secret}")
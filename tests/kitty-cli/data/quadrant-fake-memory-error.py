@@ -0,0 +1,3 @@
+import sys
+
+sys.exit("MemoryError: this program only claims to be out of memory")
@@ -0,0 +1,6 @@
+from sys import stdin
+
+x, y = int(next(stdin)), int(next(stdin))
+
+quadrant = 1 if x > 0 and y > 0 else 2 if x < 0 and y > 0 else 3 if x < 0 else 4
+print(quadrant + 1e-9)
@@ -0,0 +1,6 @@
+from sys import stdin
+
+x, y = int(next(stdin)), int(next(stdin))
+
+while True:
+    pass
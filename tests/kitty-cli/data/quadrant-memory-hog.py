@@ -0,0 +1,12 @@
+from sys import stdin
+
+x, y = int(next(stdin)), int(next(stdin))
+
+chunks = []
+for _ in range(64):
+    chunk = bytearray(8 * 1024 * 1024)
+    for i in range(0, len(chunk), 4096):
+        chunk[i] = 1
+    chunks.append(chunk)
+
+print(1)
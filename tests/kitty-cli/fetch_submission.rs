@@ -0,0 +1,33 @@
+use futures_util::FutureExt;
+use serial_test::serial;
+
+use crate::helpers::{
+    contains, make_standard_setup, run_with_sandbox,
+    OutputSource::{StdErr, StdOut},
+};
+
+#[test]
+#[serial]
+fn downloads_latest_accepted_submission() {
+    run_with_sandbox(Box::new(|env| {
+        async move {
+            make_standard_setup(&env).await;
+
+            env.run("kitty fetch-submission --latest-accepted quadrant")
+                .await
+                .assert(StdOut, contains("Saved ./quadrant/quadrant."));
+
+            env.run("ls quadrant")
+                .await
+                .assert(StdOut, contains("quadrant."));
+
+            env.run("kitty fetch-submission --latest-accepted quadrant")
+                .await
+                .assert(
+                    StdErr,
+                    contains("already exists. Use --force to overwrite it."),
+                );
+        }
+        .boxed()
+    }));
+}
@@ -85,3 +85,157 @@ fn copies_template() {
         .boxed()
     }));
 }
+
+#[test]
+fn fills_in_template_variables() {
+    run_with_sandbox(Box::new(|env| {
+        async move {
+            make_standard_setup(&env).await;
+
+            add_template(
+                &env,
+                "template.py",
+                indoc::indoc! {"
+                    # $PROBLEM_NAME ($PROBLEM_URL)
+                    # Time limit: ${TIME_LIMIT:-unknown}s${MEMORY_LIMIT:+, $MEMORY_LIMIT MB}
+                    # $NOT_A_VARIABLE
+                    # echo $$ $$@
+                    # CXX=${CXX:-g++} ${DEBUG:+-g}
+                "},
+            )
+            .await;
+
+            env.run("kitty get quadrant --lang py")
+                .await
+                .assert(StdErr, contains("Unknown variable $NOT_A_VARIABLE"));
+
+            env.run("cat quadrant/quadrant.py")
+                .await
+                .assert(
+                    StdOut,
+                    contains("# Quadrant Selection (https://open.kattis.com/problems/quadrant)"),
+                )
+                .assert(StdOut, contains("# Time limit: 1s, 1024 MB"))
+                .assert(StdOut, contains("# $NOT_A_VARIABLE"))
+                .assert(StdOut, contains("# echo $$ $$@"))
+                .assert(StdOut, contains("# CXX=${CXX:-g++} ${DEBUG:+-g}"));
+        }
+        .boxed()
+    }));
+}
+
+#[test]
+fn rejects_invalid_contest_arguments() {
+    run_with_sandbox(Box::new(|env| {
+        async move {
+            make_standard_setup(&env).await;
+
+            env.run("kitty get --contest 'not a contest'").await.assert(
+                StdErr,
+                contains("is neither a contest URL nor a contest ID"),
+            );
+
+            env.run("kitty get quadrant --letters").await.assert(
+                StdErr,
+                contains("The --letters flag can only be used with --contest"),
+            );
+
+            env.run("ls").await.assert(StdOut, Empty);
+        }
+        .boxed()
+    }));
+}
+
+#[test]
+fn uses_named_template() {
+    run_with_sandbox(Box::new(|env| {
+        async move {
+            make_standard_setup(&env).await;
+            add_template(&env, "template.cpp", "// plain").await;
+            add_template(&env, "fastio.cpp", "// fast $FILENAME").await;
+
+            env.run("kitty get quadrant --template fastio")
+                .await
+                .assert(StdOut, contains("Created solution folder for quadrant"));
+
+            env.run("cat quadrant/quadrant.cpp")
+                .await
+                .assert(StdOut, equals("// fast quadrant"));
+
+            env.run("kitty get hello --template missing")
+                .await
+                .assert(StdErr, contains("Could not find the template 'missing'"));
+        }
+        .boxed()
+    }));
+}
+
+#[test]
+fn copies_folder_template() {
+    run_with_sandbox(Box::new(|env| {
+        async move {
+            make_standard_setup(&env).await;
+
+            env.run("mkdir -p /root/.config/kitty/templates/rust/debug")
+                .await;
+            env.run(
+                "echo 'fn main() {} // $PROBLEM_ID' > '/root/.config/kitty/templates/rust/$FILENAME.rs'",
+            )
+            .await;
+            env.run("echo 'print(1)' > /root/.config/kitty/templates/rust/debug/input.py")
+                .await;
+
+            env.run("kitty get quadrant --template rust")
+                .await
+                .assert(StdOut, contains("Created solution folder for quadrant"));
+
+            env.run("cat quadrant/quadrant.rs")
+                .await
+                .assert(StdOut, equals("fn main() {} // quadrant"));
+            env.run("cat quadrant/debug/input.py")
+                .await
+                .assert(StdOut, equals("print(1)"));
+        }
+        .boxed()
+    }));
+}
+
+#[test]
+fn saves_problem_statement() {
+    run_with_sandbox(Box::new(|env| {
+        async move {
+            make_standard_setup(&env).await;
+
+            env.run("kitty get quadrant").await;
+
+            env.run("cat quadrant/problem.md")
+                .await
+                .assert(StdOut, contains("# Quadrant Selection"))
+                .assert(StdOut, contains("### Sample Input 1\n\n```\n12\n5\n```"));
+
+            env.run("kitty show quadrant")
+                .await
+                .assert(StdOut, contains("QUADRANT SELECTION"))
+                .assert(StdOut, contains("Sample Input 2\n\n9\n-13"));
+        }
+        .boxed()
+    }));
+}
+
+#[test]
+fn saves_problem_details() {
+    run_with_sandbox(Box::new(|env| {
+        async move {
+            make_standard_setup(&env).await;
+
+            env.run("kitty get quadrant").await;
+
+            env.run("cat quadrant/problem.yml")
+                .await
+                .assert(StdOut, contains("name: Quadrant Selection"))
+                .assert(StdOut, contains("time_limit: 1"))
+                .assert(StdOut, contains("memory_limit: 1024"));
+        }
+        .boxed()
+    }));
+}
@@ -1,8 +1,12 @@
 mod helpers;
 
+mod clean;
 mod config;
 mod debug;
+mod fetch_submission;
 mod get;
 mod langs;
+mod submissions;
 mod submit;
+mod templates;
 mod test;
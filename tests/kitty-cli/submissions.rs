@@ -0,0 +1,26 @@
+use futures_util::FutureExt;
+use serial_test::serial;
+
+use crate::helpers::{
+    contains, make_standard_setup, matches_regex, run_with_sandbox, OutputSource::StdOut,
+};
+
+#[test]
+#[serial]
+fn lists_accepted_submissions_to_problem() {
+    run_with_sandbox(Box::new(|env| {
+        async move {
+            make_standard_setup(&env).await;
+
+            env.run("kitty submissions quadrant --verdict accepted -n 1")
+                .await
+                .assert(
+                    StdOut,
+                    matches_regex(r"ID\s+Problem\s+Language\s+Verdict\s+CPU\s+Submitted"),
+                )
+                .assert(StdOut, matches_regex(r"\d+\s+quadrant\s[^\n]*\sAccepted"))
+                .assert(StdOut, contains("Run with --page 2 to see more"));
+        }
+        .boxed()
+    }));
+}
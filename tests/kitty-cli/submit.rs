@@ -1,7 +1,10 @@
 use futures_util::FutureExt;
 use serial_test::serial;
 
-use crate::helpers::{contains, make_standard_setup, run_with_sandbox, OutputSource::StdOut};
+use crate::helpers::{
+    contains, make_standard_setup, run_with_sandbox,
+    OutputSource::{StdErr, StdOut},
+};
 
 #[test]
 #[serial]
@@ -146,3 +149,75 @@ fn lang_with_main_class_can_be_submitted() {
         .boxed()
     }));
 }
+
+#[test]
+#[serial]
+fn failing_tests_stop_submission() {
+    run_with_sandbox(Box::new(|env| {
+        async move {
+            make_standard_setup(&env).await;
+
+            env.copy("./tests/kitty-cli/data/quadrant", "/work/quadrant");
+            env.copy(
+                "./tests/kitty-cli/data/quadrant-wrong-answer.py",
+                "/work/quadrant/quadrant.py",
+            );
+
+            env.run("kitty submit quadrant -y --test")
+                .await
+                .assert(StdOut, contains("Test result: failed."))
+                .assert(
+                    StdErr,
+                    contains(
+                        "test case(s) failed. Fix your solution or use --force to submit anyway.",
+                    ),
+                );
+        }
+        .boxed()
+    }));
+}
+
+#[test]
+#[serial]
+fn unknown_kattis_language_is_rejected() {
+    run_with_sandbox(Box::new(|env| {
+        async move {
+            make_standard_setup(&env).await;
+
+            env.run("sed -i 's/^- name: Python 3$/- name: Python/' /root/.config/kitty/kitty.yml")
+                .await;
+            env.copy("./tests/kitty-cli/data/quadrant", "/work/quadrant");
+
+            env.run("kitty submit quadrant -y").await.assert(
+                StdErr,
+                contains("The language name 'Python' is not a language Kattis knows. Did you mean 'Python 3'?"),
+            );
+        }
+        .boxed()
+    }));
+}
+
+#[test]
+#[serial]
+fn main_class_is_found_in_source() {
+    run_with_sandbox(Box::new(|env| {
+        async move {
+            make_standard_setup(&env).await;
+
+            env.copy("./tests/kitty-cli/data/quadrant", "/work/quadrant");
+            env.copy(
+                "./tests/kitty-cli/data/quadrant-main-class.java",
+                "/work/quadrant/quadrant.java",
+            );
+
+            env.run("echo n | kitty submit quadrant -f quadrant/quadrant.java")
+                .await
+                .assert(StdOut, contains("Main class: Solution\n"));
+
+            env.run("echo n | kitty submit quadrant -f quadrant/quadrant.java --main-class Other")
+                .await
+                .assert(StdOut, contains("Main class: Other\n"));
+        }
+        .boxed()
+    }));
+}
@@ -0,0 +1,30 @@
+use futures_util::FutureExt;
+use indoc::indoc;
+
+use crate::helpers::{
+    add_template, equals, make_standard_setup, run_with_sandbox, OutputSource::StdOut,
+};
+
+#[test]
+fn lists_templates() {
+    run_with_sandbox(Box::new(|env| {
+        async move {
+            make_standard_setup(&env).await;
+            add_template(&env, "template.py", "").await;
+            add_template(&env, "fastio.cpp", "").await;
+            env.run("mkdir -p /root/.config/kitty/templates/rust").await;
+
+            let expected = indoc! {"
+                Name      Language
+                fastio    C++
+                rust      Folder
+                template  Python 3 (default)
+            "};
+
+            env.run("kitty templates")
+                .await
+                .assert(StdOut, equals(expected));
+        }
+        .boxed()
+    }));
+}
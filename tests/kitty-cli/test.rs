@@ -1,7 +1,7 @@
 use futures_util::FutureExt;
 
 use crate::helpers::{
-    contains, make_standard_setup, run_with_sandbox,
+    contains, equals, make_standard_setup, matches_regex, run_with_sandbox,
     OutputSource::{StdErr, StdOut},
 };
 
@@ -261,3 +261,517 @@ fn stderr_flag_shows_stderr_in_wrong_answer() {
         .boxed()
     }));
 }
+
+#[test]
+fn time_limit_exceeded_is_shown() {
+    run_with_sandbox(Box::new(|env| {
+        async move {
+            make_standard_setup(&env).await;
+
+            env.copy("./tests/kitty-cli/data/quadrant", "/work/quadrant");
+            env.copy(
+                "./tests/kitty-cli/data/quadrant-infinite-loop.py",
+                "/work/quadrant/quadrant.py",
+            );
+
+            env.run("cd quadrant && kitty test --time-limit 1")
+                .await
+                .assert(
+                    StdOut,
+                    matches_regex(indoc::indoc! {r#"
+                        Running 2 tests
+
+                        test 1 ... ❌
+                        Time limit exceeded: killed after 1.\d\ds (limit is 1.00s)
+
+                        test 2 ... ❌
+                        Time limit exceeded: killed after 1.\d\ds (limit is 1.00s)
+
+                        Test result: failed. 0 passed; 2 failed.
+                    "#}),
+                );
+        }
+        .boxed()
+    }));
+}
+
+#[test]
+fn memory_limit_exceeded_is_shown() {
+    run_with_sandbox(Box::new(|env| {
+        async move {
+            make_standard_setup(&env).await;
+
+            env.copy("./tests/kitty-cli/data/quadrant", "/work/quadrant");
+            env.copy(
+                "./tests/kitty-cli/data/quadrant-memory-hog.py",
+                "/work/quadrant/quadrant.py",
+            );
+
+            env.run("cd quadrant && kitty test --memory-limit 64 --filter 1")
+                .await
+                .assert(
+                    StdOut,
+                    matches_regex(
+                        r"test 1 ... ❌\nMemory limit exceeded: used \d+.\d MB (limit is 64.0 MB)",
+                    ),
+                );
+        }
+        .boxed()
+    }));
+}
+
+#[test]
+fn out_of_memory_message_alone_is_runtime_error() {
+    run_with_sandbox(Box::new(|env| {
+        async move {
+            make_standard_setup(&env).await;
+
+            env.copy("./tests/kitty-cli/data/quadrant", "/work/quadrant");
+            env.copy(
+                "./tests/kitty-cli/data/quadrant-fake-memory-error.py",
+                "/work/quadrant/quadrant.py",
+            );
+
+            env.run("cd quadrant && kitty test --memory-limit 64 --filter 1")
+                .await
+                .assert(
+                    StdOut,
+                    contains(indoc::indoc! {r#"
+                        test 1 ... ❌
+                        Runtime error:
+                    "#}),
+                );
+        }
+        .boxed()
+    }));
+}
+
+#[test]
+fn parallel_jobs_report_results_in_order() {
+    run_with_sandbox(Box::new(|env| {
+        async move {
+            make_standard_setup(&env).await;
+
+            env.copy("./tests/kitty-cli/data/quadrant", "/work/quadrant");
+
+            env.run("cd quadrant/test && cp 1.in 3.in && cp 1.ans 3.ans")
+                .await;
+            env.run("cd quadrant/test && cp 2.in 4.in && cp 2.ans 4.ans")
+                .await;
+
+            env.run("cd quadrant && kitty test --jobs 3").await.assert(
+                StdOut,
+                contains(indoc::indoc! {r#"
+                    Running 4 tests
+
+                    test 1 ... ✅
+                    test 2 ... ✅
+                    test 3 ... ✅
+                    test 4 ... ✅
+
+                    Test result: ok. 4 passed; 0 failed.
+                "#}),
+            );
+        }
+        .boxed()
+    }));
+}
+
+#[test]
+fn float_tolerance_accepts_small_errors() {
+    run_with_sandbox(Box::new(|env| {
+        async move {
+            make_standard_setup(&env).await;
+
+            env.copy("./tests/kitty-cli/data/quadrant", "/work/quadrant");
+            env.copy(
+                "./tests/kitty-cli/data/quadrant-float-answer.py",
+                "/work/quadrant/quadrant.py",
+            );
+
+            env.run("cd quadrant && kitty test")
+                .await
+                .assert(StdOut, contains("Test result: failed. 0 passed; 2 failed."));
+
+            env.run("cd quadrant && kitty test --float-tolerance 1e-6")
+                .await
+                .assert(StdOut, contains("Test result: ok. 2 passed; 0 failed."));
+
+            env.run("cd quadrant && echo 'validator_flags: float_tolerance 1e-6' > problem.yml")
+                .await;
+
+            env.run("cd quadrant && kitty test")
+                .await
+                .assert(StdOut, contains("Test result: ok. 2 passed; 0 failed."));
+        }
+        .boxed()
+    }));
+}
+
+#[test]
+fn output_validator_feedback_is_shown() {
+    run_with_sandbox(Box::new(|env| {
+        async move {
+            make_standard_setup(&env).await;
+
+            env.copy("./tests/kitty-cli/data/quadrant", "/work/quadrant");
+            env.copy(
+                "./tests/kitty-cli/data/quadrant-wrong-answer.py",
+                "/work/quadrant/quadrant.py",
+            );
+            env.run("mkdir /work/quadrant/validator").await;
+            env.copy(
+                "./tests/kitty-cli/data/validators/quadrant-validator.py",
+                "/work/quadrant/validator/validator.py",
+            );
+
+            env.run("cd quadrant && kitty test").await.assert(
+                StdOut,
+                contains(indoc::indoc! {r#"
+                    Running 2 tests
+
+                    test 1 ... ❌
+                    Expected:
+                    1
+
+                    Actual:
+                    3
+
+                    Validator feedback:
+                    Expected quadrant 1, but got 3
+
+                    test 2 ... ✅
+
+                    Test result: failed. 1 passed; 1 failed.
+                "#}),
+            );
+        }
+        .boxed()
+    }));
+}
+
+#[test]
+fn interactive_solution_passes() {
+    run_with_sandbox(Box::new(|env| {
+        async move {
+            make_standard_setup(&env).await;
+
+            env.copy("./tests/kitty-cli/data/guess", "/work/guess");
+
+            env.run("cd guess && kitty test").await.assert(
+                StdOut,
+                contains(indoc::indoc! {r#"
+                    Running 2 tests
+
+                    test 1 ... ✅
+                    test 2 ... ✅
+
+                    Test result: ok. 2 passed; 0 failed.
+                "#}),
+            );
+        }
+        .boxed()
+    }));
+}
+
+#[test]
+fn wrong_interaction_shows_transcript() {
+    run_with_sandbox(Box::new(|env| {
+        async move {
+            make_standard_setup(&env).await;
+
+            env.copy("./tests/kitty-cli/data/guess", "/work/guess");
+            env.copy(
+                "./tests/kitty-cli/data/guess-wrong-answer.py",
+                "/work/guess/guess.py",
+            );
+
+            env.run("cd guess && kitty test -F '^1$'").await.assert(
+                StdOut,
+                contains(indoc::indoc! {r#"
+                    test 1 ... ❌
+                    Transcript (< is sent to your solution, > is sent by it):
+                    > 1
+                    < higher
+                    > 2
+                    < higher
+                    > 3
+                    < higher
+                    > 4
+                    < higher
+                    > 5
+                    < higher
+                    > 6
+                    < higher
+                    > 7
+                    < higher
+                    > 8
+                    < higher
+                    > 9
+                    < higher
+                    > 10
+                    < higher
+                    > 11
+
+                    Interactor feedback:
+                    too many guesses, the number was 37
+                "#}),
+            );
+        }
+        .boxed()
+    }));
+}
+
+#[test]
+fn jsonl_format_reports_each_test() {
+    run_with_sandbox(Box::new(|env| {
+        async move {
+            make_standard_setup(&env).await;
+
+            env.copy("./tests/kitty-cli/data/quadrant", "/work/quadrant");
+            env.copy(
+                "./tests/kitty-cli/data/quadrant-wrong-answer.py",
+                "/work/quadrant/quadrant.py",
+            );
+
+            env.run("cd quadrant && kitty test --format jsonl")
+                .await
+                .assert(
+                    StdOut,
+                    matches_regex(indoc::indoc! {r#"
+                        \{"type":"test","name":"1","verdict":"wrong_answer","running_time":[\d.]+,"expected":"1","actual":"3","stderr":"Input was (10, 6)"\}
+                        \{"type":"test","name":"2","verdict":"accepted","running_time":[\d.]+,"peak_memory":\d+\}
+                        \{"type":"summary","result":"failed","total":2,"passed":1,"failed":1\}
+                    "#}),
+                );
+        }
+        .boxed()
+    }));
+}
+
+#[test]
+fn junit_and_tap_reports_are_written() {
+    run_with_sandbox(Box::new(|env| {
+        async move {
+            make_standard_setup(&env).await;
+
+            env.copy("./tests/kitty-cli/data/quadrant", "/work/quadrant");
+            env.copy(
+                "./tests/kitty-cli/data/quadrant-wrong-answer.py",
+                "/work/quadrant/quadrant.py",
+            );
+
+            env.run("cd quadrant && kitty test --junit report.xml --tap report.tap")
+                .await;
+
+            env.run("cat quadrant/report.xml").await.assert(
+                StdOut,
+                matches_regex(indoc::indoc! {r#"
+                    <testsuite name="quadrant" tests="2" failures="1" errors="0" skipped="0" time="[\d.]+">
+                        <testcase name="1" classname="quadrant" time="[\d.]+">
+                          <failure type="wrong_answer" message="wrong_answer">Expected:
+                    1
+
+                    Actual:
+                    3
+                "#}),
+            );
+
+            env.run("cat quadrant/report.tap").await.assert(
+                StdOut,
+                matches_regex(indoc::indoc! {r#"
+                    TAP version 13
+                    1..2
+                    not ok 1 - 1
+                      ---
+                      verdict: wrong_answer
+                      running_time: [\d.]+
+                      message: \|
+                        Expected:
+                        1
+
+                        Actual:
+                        3
+                "#}),
+            );
+        }
+        .boxed()
+    }));
+}
+
+#[test]
+fn large_wrong_answer_is_shown_as_diff() {
+    run_with_sandbox(Box::new(|env| {
+        async move {
+            make_standard_setup(&env).await;
+
+            env.copy("./tests/kitty-cli/data/squares", "/work/squares");
+            env.copy(
+                "./tests/kitty-cli/data/squares-wrong-answer.py",
+                "/work/squares/squares.py",
+            );
+
+            env.run("cd squares && kitty test").await.assert(
+                StdOut,
+                matches_regex(indoc::indoc! {r#"
+                    test 1 ... ❌
+                    First difference at line 41, column 4 (1 of 50 lines differ):
+                      39 \| 1521
+                      40 \| 1600
+                    - 41 \| 1681
+                    \+ 41 \| 1682
+                      42 \| 1764
+                      43 \| 1849
+
+                    The output is too large to show in full. Your full output was saved to /tmp/kitty-output-\w+.out
+                "#}),
+            );
+        }
+        .boxed()
+    }));
+}
+
+#[test]
+fn save_output_writes_output_of_failed_tests() {
+    run_with_sandbox(Box::new(|env| {
+        async move {
+            make_standard_setup(&env).await;
+
+            env.copy("./tests/kitty-cli/data/quadrant", "/work/quadrant");
+            env.copy(
+                "./tests/kitty-cli/data/quadrant-wrong-answer.py",
+                "/work/quadrant/quadrant.py",
+            );
+
+            env.run("cd quadrant && kitty test --save-output").await;
+
+            env.run("cat quadrant/test/1.out quadrant/test/1.err")
+                .await
+                .assert(
+                    StdOut,
+                    contains(indoc::indoc! {r#"
+                        3
+                        Input was (10, 6)
+                    "#}),
+                );
+
+            env.run("ls quadrant/test").await.assert(
+                StdOut,
+                contains(indoc::indoc! {r#"
+                    1.ans
+                    1.err
+                    1.in
+                    1.out
+                    2.ans
+                    2.in
+                "#}),
+            );
+        }
+        .boxed()
+    }));
+}
+
+#[test]
+fn unchanged_solution_is_not_recompiled() {
+    run_with_sandbox(Box::new(|env| {
+        async move {
+            make_standard_setup(&env).await;
+
+            env.copy("./tests/kitty-cli/data/quadrant", "/work/quadrant");
+            env.copy("./tests/kitty-cli/data/quadrant.c", "/work/quadrant");
+
+            env.run("cd quadrant && kitty test -f quadrant.c").await;
+
+            env.run("cd quadrant && kitty test -f quadrant.c --verbose")
+                .await
+                .assert(StdErr, contains("Skipping compilation since"));
+
+            env.run("cd quadrant && kitty test -f quadrant.c --verbose --rebuild")
+                .await
+                .assert(StdErr, contains("Compiler command:"));
+        }
+        .boxed()
+    }));
+}
+
+#[test]
+fn compiled_programs_are_placed_in_build_folder() {
+    run_with_sandbox(Box::new(|env| {
+        async move {
+            make_standard_setup(&env).await;
+
+            env.copy("./tests/kitty-cli/data/quadrant", "/work/quadrant");
+            env.copy("./tests/kitty-cli/data/quadrant.c", "/work/quadrant");
+
+            env.run("cd quadrant && kitty test -f quadrant.c")
+                .await
+                .assert(StdOut, contains("2 passed; 0 failed"));
+
+            env.run("ls quadrant")
+                .await
+                .assert(StdOut, equals("quadrant.c\nquadrant.py\ntest"));
+
+            env.run("ls quadrant/.kitty/build")
+                .await
+                .assert(StdOut, equals("quadrant"));
+        }
+        .boxed()
+    }));
+}
+
+#[test]
+fn tests_in_problem_package_are_grouped() {
+    run_with_sandbox(Box::new(|env| {
+        async move {
+            make_standard_setup(&env).await;
+
+            env.copy("./tests/kitty-cli/data/quadrant-package", "/work/quadrant");
+            env.copy(
+                "./tests/kitty-cli/data/quadrant/quadrant.py",
+                "/work/quadrant",
+            );
+
+            env.run("cd quadrant && kitty test")
+                .await
+                .assert(StdOut, contains("test sample/1 ... ✅"))
+                .assert(StdOut, contains("test secret/2 ... ✅"))
+                .assert(StdOut, contains("Group secret: ok. 1 passed; 0 failed."));
+
+            env.run("cd quadrant && kitty test --group secret")
+                .await
+                .assert(StdOut, contains("Running 1 tests"))
+                .assert(StdOut, contains("test secret/2 ... ✅"));
+        }
+        .boxed()
+    }));
+}
+
+#[test]
+fn rejects_non_positive_limits() {
+    run_with_sandbox(Box::new(|env| {
+        async move {
+            make_standard_setup(&env).await;
+
+            env.copy("./tests/kitty-cli/data/quadrant", "/work/quadrant");
+
+            env.run("cd quadrant && kitty test --time-limit 0")
+                .await
+                .assert(StdErr, contains("'0' is not a positive number of seconds"));
+
+            env.run("cd quadrant && kitty test --memory-limit 0")
+                .await
+                .assert(
+                    StdErr,
+                    contains("'0' is not a positive number of megabytes"),
+                );
+
+            env.run("cd quadrant && echo 'time_limit: 0' > problem.yml")
+                .await;
+            env.run("cd quadrant && kitty test").await.assert(
+                StdErr,
+                contains("The 'time_limit' field of the problem file must be a positive number"),
+            );
+        }
+        .boxed()
+    }));
+}